@@ -2,12 +2,15 @@ pub use std::{
     ffi::{c_char, c_int, c_void, CString},
     mem::{size_of, transmute},
     slice,
+    sync::LazyLock,
     sync::Once,
     sync::OnceLock,
 };
 
 pub use parking_lot::Mutex;
-pub use std::ptr::{from_mut, null_mut};
+
+/// Pointer utilities, re-exported so files do not have to import them from `std::ptr` one by one.
+pub use std::ptr::{addr_of, addr_of_mut, from_mut, from_ref, null, null_mut};
 
 /// A macro to cast a raw pointer to a specific type.
 ///
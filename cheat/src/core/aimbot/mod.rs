@@ -0,0 +1,306 @@
+use egui::{Color32, Context, LayerId, Stroke};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicI32, AtomicUsize, Ordering};
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_XBUTTON2;
+
+use crate::{
+    common::c_void,
+    core::{
+        settings::{FeatureId, Settings},
+        stats,
+    },
+    cs2::{
+        entities::{skeleton::Bone, weapon::WeaponSlot, EntityIdentity},
+        input::CCSGOInput,
+        interfaces, math, view_matrix,
+    },
+    utils::render::win32::is_key_down,
+};
+
+/// Largest pitch the game accepts, in degrees.
+const MAX_PITCH: f32 = 89.0;
+
+/// The game's vertical field of view at the default horizontal 90 degrees on a 4:3 screen, in
+/// degrees. The horizontal field of view widens on wider screens while this stays the same.
+const VERTICAL_FOV: f32 = 73.74;
+
+/// Width of the FOV circle outline, in points.
+const FOV_CIRCLE_THICKNESS: f32 = 1.0;
+
+/// The entity the aimbot aimed at in the previous command, or 0 if it aimed at nothing. Used to
+/// count the targets it locked onto.
+static LAST_TARGET: AtomicUsize = AtomicUsize::new(0);
+
+/// The local player's `m_iShotsFired` in the previous command the aim key was held in, or -1 if
+/// the key was not held. Used to count the shots fired while the aimbot aimed.
+static LAST_SHOTS_FIRED: AtomicI32 = AtomicI32::new(-1);
+
+/// The bone the aimbot aims at.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AimbotBone {
+    #[default]
+    Head,
+    Neck,
+    Chest,
+}
+
+impl AimbotBone {
+    /// Every bone, in the order they are listed in the menu.
+    pub const ALL: [Self; 3] = [Self::Head, Self::Neck, Self::Chest];
+
+    /// Returns the label shown for the bone in the menu.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Head => "head",
+            Self::Neck => "neck",
+            Self::Chest => "chest",
+        }
+    }
+
+    /// Returns the skeleton bone of the player's model.
+    #[must_use]
+    pub const fn bone(self) -> Bone {
+        match self {
+            Self::Head => Bone::Head,
+            Self::Neck => Bone::Neck,
+            Self::Chest => Bone::Chest,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AimbotSettings {
+    pub enabled: bool,
+
+    /// Largest angle between the crosshair and a target, in degrees.
+    pub fov: f32,
+
+    /// Number of commands the aim is spread over, at least `AimbotSettings::MIN_SMOOTHING`. A
+    /// smoothing of 1 snaps onto the target.
+    pub smoothing: f32,
+
+    pub bone: AimbotBone,
+
+    /// Virtual-key code of the key that has to be held for the aimbot to aim.
+    pub key: u32,
+
+    /// Draws the `fov` around the crosshair while the aim key is held.
+    pub show_fov_circle: bool,
+
+    /// Keeps the FOV circle on screen when the aim key is not held.
+    pub fov_circle_always_on: bool,
+
+    pub fov_circle_color: Color32,
+}
+
+impl AimbotSettings {
+    pub const MIN_SMOOTHING: f32 = 1.0;
+    pub const MAX_SMOOTHING: f32 = 20.0;
+    pub const MAX_FOV: f32 = 30.0;
+}
+
+impl Default for AimbotSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            fov: 5.0,
+            smoothing: 4.0,
+            bone: AimbotBone::default(),
+            key: u32::from(VK_XBUTTON2.0),
+            show_fov_circle: true,
+            fov_circle_always_on: false,
+            fov_circle_color: Color32::from_white_alpha(128),
+        }
+    }
+}
+
+/// Aims at the enemy closest to the crosshair while the aimbot key is held. Called from
+/// `hk_create_move` before the game builds the command.
+///
+/// The target's bone is projected with the game's current view matrix to skip players behind the
+/// camera, and the closest remaining one within `AimbotSettings::fov` degrees is picked. The
+/// view angles are moved towards it by `1 / smoothing` of the way and written back to `input`.
+///
+/// # Parameters
+///
+/// * `input`: The game's `CCSGOInput`, as passed to `CreateMove`.
+/// * `aimbot`: The aimbot settings for this command, or `None` if `FeatureId::Aimbot` is disabled.
+pub fn run(input: *mut c_void, aimbot: Option<&AimbotSettings>) {
+    let Some(aimbot) = aimbot.filter(|aimbot| aimbot.enabled && is_key_down(aimbot.key)) else {
+        LAST_TARGET.store(0, Ordering::Relaxed);
+        LAST_SHOTS_FIRED.store(-1, Ordering::Relaxed);
+        return;
+    };
+
+    let new_shots = new_shots_fired();
+
+    let Some(input) = CCSGOInput::from_ptr(input) else {
+        return;
+    };
+
+    let Some([pitch, yaw, roll]) = input.view_angles() else {
+        return;
+    };
+
+    let Some((target, aim_angles)) = find_target([pitch, yaw], aimbot) else {
+        LAST_TARGET.store(0, Ordering::Relaxed);
+        return;
+    };
+
+    let mut session = stats::session_stats();
+
+    if LAST_TARGET.swap(target as usize, Ordering::Relaxed) != target as usize {
+        session.aimbot_targets_locked += 1;
+    }
+
+    session.aimbot_shots_taken += new_shots;
+    drop(session);
+
+    let [delta_pitch, delta_yaw] = math::angle_delta([pitch, yaw], aim_angles);
+    let smoothing = aimbot.smoothing.max(AimbotSettings::MIN_SMOOTHING);
+
+    let new_pitch = (pitch + delta_pitch / smoothing).clamp(-MAX_PITCH, MAX_PITCH);
+    let new_yaw = math::normalize_yaw(yaw + delta_yaw / smoothing);
+
+    input.set_view_angles([new_pitch, new_yaw, roll]);
+}
+
+/// Draws the aimbot's FOV as a circle around the center of the screen, as enabled in `settings`.
+///
+/// # Parameters
+///
+/// * `ctx`: A reference to the `Context` used for drawing.
+/// * `settings`: A reference to the current settings.
+pub fn draw_fov_circle(ctx: &Context, settings: &Settings) {
+    let aimbot = &settings.aimbot;
+
+    if !settings.features_enabled.contains(&FeatureId::Aimbot)
+        || !aimbot.enabled
+        || !aimbot.show_fov_circle
+        || !(aimbot.fov_circle_always_on || is_key_down(aimbot.key))
+    {
+        return;
+    }
+
+    let painter = ctx.layer_painter(LayerId::background());
+    let screen = painter.clip_rect();
+
+    painter.circle_stroke(
+        screen.center(),
+        fov_circle_radius(aimbot.fov, screen.height()),
+        Stroke::new(FOV_CIRCLE_THICKNESS, aimbot.fov_circle_color),
+    );
+}
+
+/// Returns the number of shots the local player fired since the previous command the aim key was
+/// held in, from the change of `m_iShotsFired`.
+///
+/// The game resets `m_iShotsFired` when the attack button is released, so a count lower than the
+/// previous one is the start of a new burst.
+fn new_shots_fired() -> u64 {
+    let shots_fired = interfaces::game_resource_service()
+        .entity_system()
+        .and_then(|entity_system| entity_system.local_player_pawn())
+        .and_then(EntityIdentity::as_player_pawn)
+        .and_then(|pawn| pawn.shots_fired())
+        .unwrap_or(-1);
+
+    let previous = LAST_SHOTS_FIRED.swap(shots_fired, Ordering::Relaxed);
+
+    let new_shots = match (previous, shots_fired) {
+        (_, ..0) | (..0, _) => 0,
+        (previous, shots_fired) if shots_fired < previous => shots_fired,
+        (previous, shots_fired) => shots_fired - previous,
+    };
+
+    u64::try_from(new_shots).unwrap_or(0)
+}
+
+/// Returns the distance from the center of the screen at which a direction `fov` degrees away from
+/// the crosshair appears, in pixels.
+fn fov_circle_radius(fov: f32, screen_height: f32) -> f32 {
+    let half_vertical_fov = (VERTICAL_FOV / 2.0).to_radians();
+
+    fov.to_radians().tan() / half_vertical_fov.tan() * screen_height / 2.0
+}
+
+/// Finds the living, non-dormant enemy whose target bone is closest to the crosshair.
+///
+/// # Parameters
+///
+/// * `view_angles`: The local player's current pitch and yaw.
+/// * `aimbot`: The aimbot settings.
+///
+/// # Returns
+///
+/// The target's entity and the view angles that aim at its bone, or `None` if no enemy is within
+/// `AimbotSettings::fov` degrees.
+fn find_target(
+    view_angles: [f32; 2],
+    aimbot: &AimbotSettings,
+) -> Option<(*const c_void, [f32; 2])> {
+    let entity_system = interfaces::game_resource_service().entity_system()?;
+    let local = entity_system.local_player_pawn()?;
+    let local_team = local.team();
+    let local_pawn = local.as_player_pawn()?;
+
+    // Knives and the bomb cannot be aimed
+    if matches!(local_pawn.get_active_weapon_slot(), Some(WeaponSlot::Knife | WeaponSlot::C4)) {
+        return None;
+    }
+
+    let eye_position = local_pawn.get_eye_position()?;
+
+    let view_matrix: [f32; 16] = view_matrix::get_current().as_flattened().try_into().ok()?;
+    let mut closest = None;
+    let mut closest_fov = aimbot.fov;
+
+    for (_, pawn) in entity_system.players() {
+        if pawn.entity == local.entity
+            || pawn.is_dormant()
+            || pawn.health().is_none_or(|health| health <= 0)
+            || (local_team.is_some() && pawn.team() == local_team)
+        {
+            continue;
+        }
+
+        let Some(bone_position) = bone_position(pawn, aimbot.bone) else {
+            continue;
+        };
+
+        // Only the sign of the clip-space `w` matters here, so any screen size will do
+        if math::world_to_screen(&view_matrix, bone_position, 1.0, 1.0).is_none() {
+            continue;
+        }
+
+        let aim_angles = math::calculate_angle(eye_position, bone_position);
+        let [delta_pitch, delta_yaw] = math::angle_delta(view_angles, aim_angles);
+        let fov = delta_pitch.hypot(delta_yaw);
+
+        if fov < closest_fov {
+            closest_fov = fov;
+            closest = Some((pawn.entity, aim_angles));
+        }
+    }
+
+    closest
+}
+
+/// Returns the world position of a bone of a player's pawn.
+fn bone_position(pawn: &EntityIdentity, bone: AimbotBone) -> Option<[f32; 3]> {
+    pawn.as_player_pawn()?.get_bone_matrix(bone.bone().index()).map(|matrix| matrix.translation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fov_circle_radius, VERTICAL_FOV};
+
+    #[test]
+    fn fov_circle_radius_matches_the_vertical_fov() {
+        assert_eq!(fov_circle_radius(0.0, 1080.0), 0.0);
+        assert!((fov_circle_radius(VERTICAL_FOV / 2.0, 1080.0) - 540.0).abs() < 1e-2);
+        assert!(fov_circle_radius(5.0, 1440.0) > fov_circle_radius(5.0, 1080.0));
+    }
+}
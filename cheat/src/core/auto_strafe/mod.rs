@@ -0,0 +1,52 @@
+use crate::{
+    common::{c_void, Mutex},
+    core::settings::MiscSettings,
+    cs2::{entities::FL_ONGROUND, input::CCSGOInput, interfaces, math},
+};
+
+/// Yaw of the view angles in the previous command, or `None` if auto-strafe was idle.
+static LAST_YAW: Mutex<Option<f32>> = Mutex::new(None);
+
+/// Strafes towards the side the mouse turns to while the local player is in the air, which gains
+/// speed with every turn. Called from `hk_create_move` before the game builds the command.
+///
+/// # Parameters
+///
+/// * `input`: The game's `CCSGOInput`, as passed to `CreateMove`.
+/// * `misc`: The misc settings for this command, or `None` if `FeatureId::AutoStrafe` is
+///   disabled.
+pub fn run(input: *mut c_void, misc: Option<&MiscSettings>) {
+    let enabled = misc.is_some_and(|misc| misc.auto_strafe);
+    let mut last_yaw = LAST_YAW.lock();
+
+    let Some(input) = CCSGOInput::from_ptr(input).filter(|_| enabled) else {
+        *last_yaw = None;
+        return;
+    };
+
+    let Some([_, yaw, _]) = input.view_angles() else {
+        *last_yaw = None;
+        return;
+    };
+
+    let previous_yaw = last_yaw.replace(yaw);
+
+    let on_ground = interfaces::game_resource_service()
+        .entity_system()
+        .and_then(|entity_system| entity_system.local_player_pawn())
+        .and_then(|local| local.flags())
+        .is_none_or(|flags| flags & FL_ONGROUND != 0);
+
+    let Some(previous_yaw) = previous_yaw.filter(|_| !on_ground) else {
+        return;
+    };
+
+    let yaw_delta = math::normalize_yaw(yaw - previous_yaw);
+
+    // Yaw grows counterclockwise, so turning the mouse left strafes left
+    if yaw_delta > 0.0 {
+        input.set_left_move(CCSGOInput::MAX_MOVE);
+    } else if yaw_delta < 0.0 {
+        input.set_left_move(-CCSGOInput::MAX_MOVE);
+    }
+}
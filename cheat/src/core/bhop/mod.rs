@@ -0,0 +1,43 @@
+use crate::{
+    common::c_void,
+    core::settings::MiscSettings,
+    cs2::{
+        entities::FL_ONGROUND,
+        input::{CCSGOInput, IN_JUMP},
+        interfaces,
+    },
+};
+
+/// Releases the jump button while the local player is in the air, so holding jump hops again on
+/// the tick the player lands instead of requiring a frame-perfect press. Called from
+/// `hk_create_move` before the game builds the command.
+///
+/// # Parameters
+///
+/// * `input`: The game's `CCSGOInput`, as passed to `CreateMove`.
+/// * `misc`: The misc settings for this command, or `None` if `FeatureId::Bhop` is disabled.
+pub fn run(input: *mut c_void, misc: Option<&MiscSettings>) {
+    if !misc.is_some_and(|misc| misc.bhop_enabled) {
+        return;
+    }
+
+    let Some(input) = CCSGOInput::from_ptr(input) else {
+        return;
+    };
+
+    let Some(buttons) = input.buttons().filter(|buttons| buttons & IN_JUMP != 0) else {
+        return;
+    };
+
+    let Some(flags) = interfaces::game_resource_service()
+        .entity_system()
+        .and_then(|entity_system| entity_system.local_player_pawn())
+        .and_then(|local| local.flags())
+    else {
+        return;
+    };
+
+    if flags & FL_ONGROUND == 0 {
+        input.set_buttons(buttons & !IN_JUMP);
+    }
+}
@@ -1,26 +1,123 @@
 use anyhow::Context;
-use tracing::Level;
-use tracing_subscriber::FmtSubscriber;
+use std::{
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+use tracing_subscriber::{
+    filter::LevelFilter,
+    fmt::{self, format::FmtSpan},
+    layer::SubscriberExt,
+    reload,
+    util::SubscriberInitExt,
+    Registry,
+};
 
 use crate::{
-    core::hooks,
+    common::OnceLock,
+    core::{crash, hooks, settings, ui},
     cs2::{self},
-    utils::render,
+    utils::{hotkeys, render},
 };
 
+/// The progress of `initialize`, as reported to the overlay.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BootstrapStatus {
+    Uninitialized,
+    Initializing,
+    Ready,
+
+    /// Initialization failed with the given error message.
+    Failed(String),
+}
+
+impl BootstrapStatus {
+    const UNINITIALIZED: u32 = 0;
+    const INITIALIZING: u32 = 1;
+    const READY: u32 = 2;
+    const FAILED: u32 = 3;
+}
+
+/// Discriminant of the current `BootstrapStatus`.
+static STATUS: AtomicU32 = AtomicU32::new(BootstrapStatus::UNINITIALIZED);
+
+/// Error message of `BootstrapStatus::Failed`. Set once, before `STATUS` changes to `FAILED`.
+static FAILURE: OnceLock<String> = OnceLock::new();
+
+/// Returns the current initialization status.
+///
+/// Safe to call from any thread, including game threads inside hooks.
+#[must_use]
+pub fn status() -> BootstrapStatus {
+    match STATUS.load(Ordering::Acquire) {
+        BootstrapStatus::INITIALIZING => BootstrapStatus::Initializing,
+        BootstrapStatus::READY => BootstrapStatus::Ready,
+        BootstrapStatus::FAILED => {
+            BootstrapStatus::Failed(FAILURE.get().cloned().unwrap_or_default())
+        }
+        _ => BootstrapStatus::Uninitialized,
+    }
+}
+
+fn set_status(status: &BootstrapStatus) {
+    let discriminant = match status {
+        BootstrapStatus::Uninitialized => BootstrapStatus::UNINITIALIZED,
+        BootstrapStatus::Initializing => BootstrapStatus::INITIALIZING,
+        BootstrapStatus::Ready => BootstrapStatus::READY,
+        BootstrapStatus::Failed(message) => {
+            // `initialize` only runs once, so there is no earlier failure to keep
+            _ = FAILURE.set(message.clone());
+            BootstrapStatus::FAILED
+        }
+    };
+
+    STATUS.store(discriminant, Ordering::Release);
+}
+
+/// Handle used to change the log level after the subscriber has been installed.
+static LOG_FILTER: OnceLock<reload::Handle<LevelFilter, Registry>> = OnceLock::new();
+
 fn init_tracing() -> anyhow::Result<()> {
-    let subscriber =
-        FmtSubscriber::builder().with_max_level(Level::TRACE).with_ansi(false).finish();
+    let level = settings::SETTINGS.lock().misc.log_level;
+    let (filter, handle) = reload::Layer::new(LevelFilter::from(level));
 
-    tracing::subscriber::set_global_default(subscriber)
+    tracing_subscriber::registry()
+        .with(filter)
+        // Log when a span closes, with the time spent in it, so startup steps can be profiled
+        .with(fmt::layer().with_ansi(false).with_span_events(FmtSpan::CLOSE))
+        .try_init()
         .context("failed to set global default tracing subscriber")?;
 
+    // `try_init` above fails on a second call, so the handle cannot be set already
+    _ = LOG_FILTER.set(handle);
+
     Ok(())
 }
 
+/// Changes the most verbose level of log messages that are written.
+///
+/// A global subscriber can only be installed once, so the filter of the installed subscriber is
+/// swapped out instead.
+///
+/// # Parameters
+///
+/// * `level`: The new log level.
+///
+/// # Errors
+///
+/// Returns an error if tracing has not been initialized or the filter could not be replaced.
+pub fn set_log_level(level: settings::LogLevel) -> anyhow::Result<()> {
+    LOG_FILTER
+        .get()
+        .context("tracing is not initialized")?
+        .reload(LevelFilter::from(level))
+        .context("failed to reload log filter")
+}
+
 /// Initializes the core components of the cheat.
 ///
 /// This function sets up the necessary modules, rendering, and hooks for the cheat to function.
+/// It moves the `BootstrapStatus` to `Initializing` while it runs, and to `Ready` or `Failed`
+/// once it is done, so the overlay can show a loading indicator or the error.
 ///
 /// # Parameters
 ///
@@ -38,16 +135,69 @@ fn init_tracing() -> anyhow::Result<()> {
 ///
 /// * `anyhow::Error`: If any of the initialization steps (`initialize_modules`, `setup`, `initialize_hooks`) fail.
 pub fn initialize() -> anyhow::Result<()> {
-    tracing::info!("initializing core components...");
+    set_status(&BootstrapStatus::Initializing);
+
+    let result = initialize_components();
+
+    match &result {
+        Ok(()) => set_status(&BootstrapStatus::Ready),
+        Err(e) => set_status(&BootstrapStatus::Failed(format!("{e:#}"))),
+    }
+
+    result
+}
+
+fn initialize_components() -> anyhow::Result<()> {
+    // Load the config first, so the configured log level applies from the start
+    let config = settings::load();
 
     init_tracing().context("failed to initialize tracing")?;
 
-    cs2::modules::initialize_modules(&["client.dll", "engine2.dll", "gameoverlayrenderer64.dll"])
-        .context("failed to initialize modules")?;
+    // Groups the log entries of every initialization step, and logs the total time when it closes
+    let _span = tracing::info_span!("bootstrap").entered();
 
+    tracing::info!("initializing core components...");
+
+    match config {
+        Ok(()) => tracing::info!("loaded config from {}", settings::config_path().display()),
+        Err(e) => tracing::info!("using default settings: {e:#}"),
+    }
+
+    settings::auto_save(Duration::from_secs(5));
+
+    // The menu rebinds the hotkey when the key is changed in the settings
+    let menu_key = settings::SETTINGS.lock().misc.menu_key;
+    hotkeys::HOTKEYS.lock().register(menu_key, ui::toggle_menu);
+
+    crash::install_panic_hook();
+
+    tracing::info!("initializing modules...");
+    // tier0.dll is only needed for the convar dump
+    cs2::modules::initialize_modules(
+        &["client.dll", "engine2.dll", "gameoverlayrenderer64.dll"],
+        &["tier0.dll"],
+    )
+    .context("failed to initialize modules")?;
+
+    // Missing offsets are reported after the hooks are installed, so the overlay hooks still go
+    // in and can show the error
+    tracing::info!("resolving offsets...");
+    let offsets = cs2::offsets::initialize();
+
+    // Features that need the local player skip it when it is missing, so this is not fatal
+    if let Err(e) = cs2::local_player::initialize() {
+        tracing::warn!("failed to initialize local player: {e:#}");
+    }
+
+    if let Err(e) = cs2::view_matrix::initialize() {
+        tracing::warn!("failed to initialize view matrix: {e:#}");
+    }
+
+    tracing::info!("setting up renderer...");
     render::setup().context("failed to setup renderer")?;
 
+    tracing::info!("installing hooks...");
     hooks::initialize_hooks().context("failed to initialize hooks")?;
 
-    Ok(())
+    offsets.context("failed to resolve offsets")
 }
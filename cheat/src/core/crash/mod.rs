@@ -0,0 +1,215 @@
+use std::{
+    backtrace::Backtrace,
+    panic::{self, AssertUnwindSafe, PanicHookInfo},
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use windows::{
+    core::{HSTRING, PCWSTR},
+    Win32::{
+        Foundation::{CloseHandle, HMODULE},
+        Storage::FileSystem::{
+            CreateFileW, CREATE_ALWAYS, FILE_ATTRIBUTE_NORMAL, FILE_GENERIC_WRITE, FILE_SHARE_NONE,
+        },
+        System::{
+            Diagnostics::Debug::{MiniDumpWithThreadInfo, MiniDumpWriteDump},
+            LibraryLoader::{
+                FreeLibraryAndExitThread, GetModuleHandleExW,
+                GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS,
+                GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT,
+            },
+            Threading::{GetCurrentProcess, GetCurrentProcessId, GetCurrentThreadId},
+        },
+    },
+};
+
+use crate::{
+    common::OnceLock,
+    core::stats,
+    utils::{hook_system, render::win32},
+};
+
+/// The ID of the thread `thread_startup` runs on, the only thread the panic hook unloads from.
+static CHEAT_THREAD: OnceLock<u32> = OnceLock::new();
+
+/// Set once a crash report was written, so later panics are only logged.
+static REPORTED: AtomicBool = AtomicBool::new(false);
+
+/// Stores the calling thread as the cheat's own thread, for the panic hook.
+///
+/// Only the first thread is kept; later calls do nothing.
+pub fn store_cheat_thread() {
+    // SAFETY: `GetCurrentThreadId` has no preconditions.
+    _ = CHEAT_THREAD.set(unsafe { GetCurrentThreadId() });
+}
+
+/// Installs a panic hook that reports panics and unloads the cheat instead of taking the game down
+/// with it.
+///
+/// When a thread panics, the hook:
+///
+/// 1. Logs the panic message, its location and a backtrace. For the first panic, it also writes
+///    them to a crash report in the temporary directory, and a minidump of the game process next
+///    to it, so a hook that panics every frame does not fill the directory.
+/// 2. Returns if the panic is not on the thread stored by `store_cheat_thread`. Game threads are
+///    left running, and the panic unwinds to the `catch_panic` around the hook body instead.
+/// 3. Removes all function hooks and restores the window procedure, so the game no longer calls
+///    into the cheat.
+/// 4. Logs the session statistics and unloads the cheat DLL with `FreeLibraryAndExitThread`,
+///    ending the panicking thread.
+pub fn install_panic_hook() {
+    panic::set_hook(Box::new(panic_hook));
+}
+
+/// Runs `f` and catches a panic in it, so it does not unwind into the game.
+///
+/// Hooks run `f` on game threads, where the panic hook only reports the panic, so they fall
+/// through to the original function when this returns `None`.
+///
+/// # Parameters
+///
+/// * `name`: What `f` does, for the log.
+/// * `f`: The code to run.
+pub fn catch_panic<T>(name: &str, f: impl FnOnce() -> T) -> Option<T> {
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+
+    if result.is_err() {
+        tracing::error!("{name} panicked, skipping it");
+    }
+
+    result.ok()
+}
+
+fn panic_hook(info: &PanicHookInfo) {
+    let message = info
+        .payload()
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| info.payload().downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("<non-string panic payload>");
+
+    let location =
+        info.location().map_or_else(|| "<unknown location>".to_owned(), ToString::to_string);
+
+    let backtrace = Backtrace::force_capture();
+
+    tracing::error!("panicked at {location}: {message}\n{backtrace}");
+
+    if !REPORTED.swap(true, Ordering::Relaxed) {
+        write_crash_report(&location, message, &backtrace);
+    }
+
+    // SAFETY: `GetCurrentThreadId` has no preconditions.
+    if CHEAT_THREAD.get() != Some(&unsafe { GetCurrentThreadId() }) {
+        return;
+    }
+
+    if let Err(e) = hook_system::uninitialize_minhook() {
+        tracing::error!("failed to remove hooks: {e:#}");
+    }
+
+    if let Err(e) = win32::destroy() {
+        tracing::error!("failed to restore window procedure: {e:#}");
+    }
+
+    match own_module() {
+        Ok(module) => {
+            stats::log_session_stats();
+            tracing::error!("unloading cheat after panic");
+
+            // SAFETY: Hooks and the window procedure no longer point into the cheat, and the
+            // reference count was not changed by `own_module`, so this drops the loader's
+            // reference and unloads the DLL.
+            unsafe { FreeLibraryAndExitThread(module, 1) }
+        }
+        Err(e) => tracing::error!("failed to get cheat module handle, cannot unload: {e:#}"),
+    }
+}
+
+/// Writes the panic to a crash report, and a minidump of the game process next to it.
+fn write_crash_report(location: &str, message: &str, backtrace: &Backtrace) {
+    let report_path = crash_file_path("log");
+    let report = format!("panicked at {location}: {message}\n\nbacktrace:\n{backtrace}\n");
+
+    match std::fs::write(&report_path, report) {
+        Ok(()) => tracing::error!("crash report written to {}", report_path.display()),
+        Err(e) => tracing::error!("failed to write crash report: {e}"),
+    }
+
+    let dump_path = crash_file_path("dmp");
+
+    match write_minidump(&dump_path) {
+        Ok(()) => tracing::error!("minidump written to {}", dump_path.display()),
+        Err(e) => tracing::error!("failed to write minidump: {e:#}"),
+    }
+}
+
+/// Returns a path in the temporary directory for a crash file with the given extension.
+fn crash_file_path(extension: &str) -> PathBuf {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+
+    // SAFETY: `GetCurrentProcessId` has no preconditions.
+    let pid = unsafe { GetCurrentProcessId() };
+
+    std::env::temp_dir().join(format!("cs2_internal-{pid}-{timestamp}.{extension}"))
+}
+
+/// Writes a minidump of the game process to `path`.
+fn write_minidump(path: &std::path::Path) -> anyhow::Result<()> {
+    let path = HSTRING::from(path.to_string_lossy().as_ref());
+
+    // SAFETY: `path` is a valid null-terminated wide string that outlives the call.
+    let file = unsafe {
+        CreateFileW(
+            &path,
+            FILE_GENERIC_WRITE.0,
+            FILE_SHARE_NONE,
+            None,
+            CREATE_ALWAYS,
+            FILE_ATTRIBUTE_NORMAL,
+            None,
+        )
+    }
+    .context("failed to create dump file")?;
+
+    // SAFETY: `file` is a handle to a writable file and the process handle is the pseudo handle
+    // of the current process.
+    let result = unsafe {
+        MiniDumpWriteDump(
+            GetCurrentProcess(),
+            GetCurrentProcessId(),
+            file,
+            MiniDumpWithThreadInfo,
+            None,
+            None,
+            None,
+        )
+    };
+
+    // SAFETY: `file` was opened above and is not used after this. A failure to close it leaks the
+    // handle, which does not matter while the process is crashing.
+    _ = unsafe { CloseHandle(file) };
+
+    result.context("MiniDumpWriteDump failed")
+}
+
+/// Returns the handle of the cheat DLL without changing its reference count.
+fn own_module() -> anyhow::Result<HMODULE> {
+    let mut module = HMODULE::default();
+
+    // SAFETY: With `GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS` the name is read as an address inside
+    // the module, and `install_panic_hook` lives in the cheat DLL.
+    unsafe {
+        GetModuleHandleExW(
+            GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS | GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT,
+            PCWSTR(install_panic_hook as *const () as *const u16),
+            &mut module,
+        )
+    }
+    .context("GetModuleHandleExW failed")?;
+
+    Ok(module)
+}
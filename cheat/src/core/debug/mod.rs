@@ -0,0 +1,98 @@
+use std::{
+    fmt::Write,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+
+use crate::cs2::interfaces;
+
+/// Writes every entity in the entity list to `%TEMP%\cs2-entities-TIMESTAMP.txt`.
+///
+/// Each line holds the entity's index, designer name, health, position and team. This is a
+/// one-shot diagnostic for checking entity reading without verbose logging.
+///
+/// # Returns
+///
+/// * `anyhow::Result<PathBuf>`: The path of the written file.
+///
+/// # Errors
+///
+/// Returns an error if the entity system is not available (for example, in the main menu) or the
+/// file could not be written.
+pub fn dump_entities() -> anyhow::Result<PathBuf> {
+    let entity_system = interfaces::game_resource_service()
+        .entity_system()
+        .context("entity system is not available")?;
+
+    let mut dump = String::new();
+    let mut count = 0;
+
+    for (index, identity) in entity_system.entities() {
+        let name = identity.designer_name().unwrap_or("<unknown>");
+        let health = identity.health().map_or_else(|| "?".to_owned(), |health| health.to_string());
+        let team = identity.team().map_or_else(|| "?".to_owned(), |team| team.to_string());
+        let origin = identity
+            .origin()
+            .map_or_else(|| "?".to_owned(), |[x, y, z]| format!("({x:.1}, {y:.1}, {z:.1})"));
+
+        // Writing to a `String` cannot fail
+        _ = writeln!(dump, "{index:5} {name:40} health={health} pos={origin} team={team}");
+        count += 1;
+    }
+
+    let path = temp_file_path("cs2-entities");
+
+    std::fs::write(&path, dump).with_context(|| format!("failed to write {}", path.display()))?;
+
+    tracing::info!("dumped {count} entities to {}", path.display());
+
+    Ok(path)
+}
+
+/// Writes every registered convar to `%TEMP%\cs2-convars-TIMESTAMP.txt`.
+///
+/// Each line holds the convar's name, value type, flags and help text, which helps finding
+/// convars by value type while reverse engineering.
+///
+/// # Returns
+///
+/// * `anyhow::Result<PathBuf>`: The path of the written file.
+///
+/// # Errors
+///
+/// Returns an error if the convar system is not available or the file could not be written.
+pub fn dump_convars() -> anyhow::Result<PathBuf> {
+    let convar_system = interfaces::convar_system().context("convar system is not available")?;
+
+    let mut dump = String::new();
+    let mut count = 0;
+
+    for convar in convar_system.iter() {
+        let name = convar.name().unwrap_or("<unknown>");
+        let value_type =
+            convar.value_type().map_or_else(|| "?".to_owned(), |value_type| value_type.to_string());
+        let flags = convar.flags().map_or_else(|| "?".to_owned(), |flags| format!("{flags:#x}"));
+        let description = convar.description().unwrap_or_default().replace('\n', " ");
+
+        // Writing to a `String` cannot fail
+        _ = writeln!(dump, "{name:48} type={value_type} flags={flags} {description}");
+        count += 1;
+    }
+
+    let path = temp_file_path("cs2-convars");
+
+    std::fs::write(&path, dump).with_context(|| format!("failed to write {}", path.display()))?;
+
+    tracing::info!("dumped {count} convars to {}", path.display());
+
+    Ok(path)
+}
+
+/// Returns a path in the temporary directory named after `prefix` and the current time.
+fn temp_file_path(prefix: &str) -> PathBuf {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+
+    std::env::temp_dir().join(format!("{prefix}-{timestamp}.txt"))
+}
@@ -0,0 +1,359 @@
+use egui::{Align2, Color32, Context, FontId, LayerId, Painter, Pos2, Rect, Stroke, Vec2};
+
+use crate::{
+    core::{
+        settings::{FeatureId, Settings},
+        stats,
+    },
+    cs2::{
+        entities::{skeleton::BONE_CONNECTIONS, weapon, CCSPlayerPawn, EntityIdentity},
+        interfaces, math, view_matrix,
+    },
+};
+
+use super::health_color;
+
+/// Height of a standing player's bounding box, in world units.
+const PLAYER_HEIGHT: f32 = 72.0;
+
+/// Width of a player's bounding box along both horizontal axes, in world units.
+const PLAYER_WIDTH: f32 = 32.0;
+
+/// Width of the box outline, in points.
+const BOX_THICKNESS: f32 = 1.0;
+
+/// Health of a player that has not been damaged.
+const MAX_HEALTH: i32 = 100;
+
+/// Width of the health bar, in points.
+const HEALTH_BAR_WIDTH: f32 = 3.0;
+
+/// Space between the health bar and the box, in points.
+const HEALTH_BAR_GAP: f32 = 3.0;
+
+/// Size of the name tag text.
+const NAME_TAG_FONT_SIZE: f32 = 13.0;
+
+/// Space between the name tag and the box, in points.
+const NAME_TAG_GAP: f32 = 2.0;
+
+/// Size of the defusing label text.
+const DEFUSING_LABEL_FONT_SIZE: f32 = 11.0;
+
+/// Color of the defusing label.
+const DEFUSING_LABEL_COLOR: Color32 = Color32::from_rgb(238, 212, 159);
+
+/// Size of the weapon label text.
+const WEAPON_LABEL_FONT_SIZE: f32 = 11.0;
+
+/// Space between the weapon label and the box, in points.
+const WEAPON_LABEL_GAP: f32 = 2.0;
+
+/// Width of a skeleton line, in points.
+const SKELETON_THICKNESS: f32 = 1.0;
+
+/// Width of a snapline, in points.
+const SNAPLINE_THICKNESS: f32 = 1.0;
+
+/// Length of a world unit (an inch), in metres.
+const METRES_PER_UNIT: f32 = 0.0254;
+
+/// Draws the ESP for every other living player, as enabled in `settings`.
+///
+/// # Parameters
+///
+/// * `ctx`: A reference to the `Context` used for drawing.
+/// * `settings`: A reference to the current settings.
+pub fn draw(ctx: &Context, settings: &Settings) {
+    let esp = &settings.visuals.esp;
+
+    if !settings.features_enabled.contains(&FeatureId::Esp)
+        || !esp.enabled
+        || !view_matrix::is_valid()
+    {
+        return;
+    }
+
+    let Some(entity_system) = interfaces::game_resource_service().entity_system() else {
+        return;
+    };
+
+    let local = entity_system.local_player_pawn();
+    let local_team = local.and_then(EntityIdentity::team);
+    let local_origin = local.and_then(EntityIdentity::origin);
+    let view_matrix = view_matrix::get();
+    let painter = ctx.layer_painter(LayerId::background());
+    let screen_bottom_center = painter.clip_rect().center_bottom();
+    let mut drawn = 0;
+
+    for (controller, pawn) in entity_system.players() {
+        if local.is_some_and(|local| local.entity == pawn.entity)
+            || !controller.is_connected()
+            || (!esp.show_bots && controller.is_bot())
+            || (!esp.show_dormant && pawn.is_dormant())
+        {
+            continue;
+        }
+
+        let Some(health) = pawn.health().filter(|&health| health > 0) else {
+            continue;
+        };
+
+        let is_teammate = local_team.is_some() && pawn.team() == local_team;
+
+        if is_teammate && !esp.show_teammates {
+            continue;
+        }
+
+        let color = if is_teammate { esp.team_color } else { esp.enemy_color };
+
+        let Some(origin) = pawn.origin() else {
+            continue;
+        };
+
+        let Some(rect) = screen_bounds(origin, &view_matrix, painter.clip_rect()) else {
+            continue;
+        };
+
+        if esp.draw_snaplines {
+            draw_snapline(&painter, screen_bottom_center, rect.center_bottom(), esp.snapline_color);
+        }
+
+        if esp.draw_skeleton {
+            if let Some(pawn) = pawn.as_player_pawn() {
+                draw_skeleton(&painter, pawn, &view_matrix, color);
+            }
+        }
+
+        if esp.draw_boxes {
+            draw_box(&painter, rect, color);
+        }
+
+        if esp.draw_health {
+            draw_health_bar(&painter, rect, health, MAX_HEALTH);
+        }
+
+        let mut label_pos = rect.center_top();
+
+        if esp.draw_nametags {
+            if let Some(name) = controller.player_name() {
+                let distance_m = local_origin.map(|local_origin| {
+                    let distance_squared: f32 =
+                        local_origin.iter().zip(origin).map(|(a, b)| (a - b).powi(2)).sum();
+
+                    distance_squared.sqrt() * METRES_PER_UNIT
+                });
+
+                draw_name_tag(&painter, label_pos, &name, distance_m);
+                label_pos.y -= NAME_TAG_GAP + NAME_TAG_FONT_SIZE;
+            }
+        }
+
+        if pawn.as_player_pawn().is_some_and(|pawn| pawn.is_defusing()) {
+            draw_defusing_label(&painter, label_pos);
+        }
+
+        if esp.draw_weapon {
+            if let Some(weapon_name) = pawn
+                .active_weapon()
+                .and_then(EntityIdentity::designer_name)
+                .and_then(weapon::display_name)
+            {
+                draw_weapon_label(&painter, rect.center_bottom(), weapon_name);
+            }
+        }
+
+        drawn += 1;
+    }
+
+    stats::session_stats().esp_entities_drawn += drawn;
+}
+
+/// Draws the outline of a player's box.
+///
+/// # Parameters
+///
+/// * `painter`: The painter to draw with.
+/// * `screen_rect`: The player's box on the screen, see `screen_bounds`.
+/// * `color`: The color of the box outline.
+pub fn draw_box(painter: &Painter, screen_rect: Rect, color: Color32) {
+    painter.rect_stroke(screen_rect, 0.0, Stroke::new(BOX_THICKNESS, color));
+}
+
+/// Draws a player's skeleton as a line for every pair of bones in `BONE_CONNECTIONS`.
+///
+/// Lines with an end that cannot be read or is behind the camera are skipped.
+///
+/// # Parameters
+///
+/// * `painter`: The painter to draw with.
+/// * `pawn`: The player's pawn.
+/// * `view_matrix`: The view matrix of the frame, see `view_matrix::get`.
+/// * `color`: The color of the lines.
+pub fn draw_skeleton(
+    painter: &Painter,
+    pawn: &CCSPlayerPawn,
+    view_matrix: &[f32; 16],
+    color: Color32,
+) {
+    let screen = painter.clip_rect();
+    let bone_on_screen = |bone_index| {
+        let position = pawn.get_bone_matrix(bone_index)?.translation;
+        let [x, y] = math::world_to_screen(view_matrix, position, screen.width(), screen.height())?;
+
+        Some(Pos2::new(screen.left() + x, screen.top() + y))
+    };
+
+    for &(parent, child) in BONE_CONNECTIONS {
+        if let (Some(parent), Some(child)) = (bone_on_screen(parent), bone_on_screen(child)) {
+            painter.line_segment([parent, child], Stroke::new(SKELETON_THICKNESS, color));
+        }
+    }
+}
+
+/// Draws a vertical health bar to the left of a player's box.
+///
+/// The bar fills from the bottom in proportion to `health`, on a black background that is a
+/// point larger on every side. Its color fades from green at full health to red at none. Health
+/// above `max_health` shows a full bar.
+///
+/// # Parameters
+///
+/// * `painter`: The painter to draw with.
+/// * `screen_rect`: The player's box on the screen, see `screen_bounds`.
+/// * `health`: The player's health.
+/// * `max_health`: The health of a full bar.
+pub fn draw_health_bar(painter: &Painter, screen_rect: Rect, health: i32, max_health: i32) {
+    let fraction = (health as f32 / max_health.max(1) as f32).clamp(0.0, 1.0);
+
+    let bar = Rect::from_min_max(
+        Pos2::new(screen_rect.left() - HEALTH_BAR_GAP - HEALTH_BAR_WIDTH, screen_rect.top()),
+        Pos2::new(screen_rect.left() - HEALTH_BAR_GAP, screen_rect.bottom()),
+    );
+    let filled =
+        Rect::from_min_max(Pos2::new(bar.left(), bar.bottom() - bar.height() * fraction), bar.max);
+
+    painter.rect_filled(bar.expand2(Vec2::splat(1.0)), 0.0, Color32::BLACK);
+    painter.rect_filled(filled, 0.0, health_color((fraction * 100.0).round() as i32));
+}
+
+/// Draws a line from the bottom center of the screen to a player.
+///
+/// # Parameters
+///
+/// * `painter`: The painter to draw with.
+/// * `screen_bottom_center`: The bottom center of the game window.
+/// * `entity_screen_pos`: The player's feet on the screen, the bottom center of their box.
+/// * `color`: The color of the line.
+pub fn draw_snapline(
+    painter: &Painter,
+    screen_bottom_center: Pos2,
+    entity_screen_pos: Pos2,
+    color: Color32,
+) {
+    painter.line_segment(
+        [screen_bottom_center, entity_screen_pos],
+        Stroke::new(SNAPLINE_THICKNESS, color),
+    );
+}
+
+/// Draws a player's name centered above their box, with a drop shadow for readability over
+/// bright backgrounds.
+///
+/// # Parameters
+///
+/// * `painter`: The painter to draw with.
+/// * `pos`: The top center of the player's box.
+/// * `name`: The player's name.
+/// * `distance_m`: The distance to the player in metres, appended to the name if known.
+pub fn draw_name_tag(painter: &Painter, pos: Pos2, name: &str, distance_m: Option<f32>) {
+    let text = match distance_m {
+        Some(distance_m) => format!("{name} [{distance_m:.1}m]"),
+        None => name.to_owned(),
+    };
+
+    draw_shadowed_text(
+        painter,
+        pos - Vec2::new(0.0, NAME_TAG_GAP),
+        Align2::CENTER_BOTTOM,
+        &text,
+        FontId::proportional(NAME_TAG_FONT_SIZE),
+        Color32::WHITE,
+    );
+}
+
+/// Draws a "DEFUSING" label centered above a player's box or name tag.
+///
+/// # Parameters
+///
+/// * `painter`: The painter to draw with.
+/// * `pos`: The top center of the player's name tag, or of their box if it has none.
+pub fn draw_defusing_label(painter: &Painter, pos: Pos2) {
+    draw_shadowed_text(
+        painter,
+        pos - Vec2::new(0.0, NAME_TAG_GAP),
+        Align2::CENTER_BOTTOM,
+        "DEFUSING",
+        FontId::proportional(DEFUSING_LABEL_FONT_SIZE),
+        DEFUSING_LABEL_COLOR,
+    );
+}
+
+/// Draws the name of a player's weapon centered below their box, such as `AK-47`.
+///
+/// # Parameters
+///
+/// * `painter`: The painter to draw with.
+/// * `pos`: The bottom center of the player's box.
+/// * `weapon_name`: The weapon's display name, see `weapon::display_name`.
+pub fn draw_weapon_label(painter: &Painter, pos: Pos2, weapon_name: &str) {
+    draw_shadowed_text(
+        painter,
+        pos + Vec2::new(0.0, WEAPON_LABEL_GAP),
+        Align2::CENTER_TOP,
+        weapon_name,
+        FontId::proportional(WEAPON_LABEL_FONT_SIZE),
+        Color32::WHITE,
+    );
+}
+
+/// Draws text with a black drop shadow, for readability over bright backgrounds.
+fn draw_shadowed_text(
+    painter: &Painter,
+    pos: Pos2,
+    anchor: Align2,
+    text: &str,
+    font: FontId,
+    color: Color32,
+) {
+    painter.text(pos + Vec2::splat(1.0), anchor, text, font.clone(), Color32::BLACK);
+    painter.text(pos, anchor, text, font, color);
+}
+
+/// Projects the 8 corners of a player's bounding box and returns the rectangle enclosing them.
+///
+/// The bounding box is a standing player's size, placed at the player's origin (their feet).
+///
+/// # Returns
+///
+/// The rectangle on the screen, or `None` if part of the box is behind the camera.
+fn screen_bounds(origin: [f32; 3], view_matrix: &[f32; 16], screen: Rect) -> Option<Rect> {
+    let [x, y, z] = origin;
+    let half_width = PLAYER_WIDTH / 2.0;
+    let mut bounds = Rect::NOTHING;
+
+    for corner in 0..8 {
+        let corner = [
+            if corner & 1 == 0 { x - half_width } else { x + half_width },
+            if corner & 2 == 0 { y - half_width } else { y + half_width },
+            if corner & 4 == 0 { z } else { z + PLAYER_HEIGHT },
+        ];
+
+        let [screen_x, screen_y] =
+            math::world_to_screen(view_matrix, corner, screen.width(), screen.height())?;
+
+        bounds.extend_with(Pos2::new(screen.left() + screen_x, screen.top() + screen_y));
+    }
+
+    Some(bounds)
+}
@@ -0,0 +1,39 @@
+use egui::{Color32, Context};
+
+use crate::core::settings::Settings;
+
+#[cfg(feature = "feature-esp")]
+pub mod esp;
+#[cfg(feature = "feature-radar")]
+pub mod radar;
+pub mod self_health;
+
+/// Draws every in-game visual feature that is enabled in `settings`.
+///
+/// Called once per overlay frame, after the menu.
+///
+/// # Parameters
+///
+/// * `ctx`: A reference to the `Context` used for drawing.
+/// * `settings`: A mutable reference to the current settings, as some features (such as the
+///   radar zoom) are adjusted in place.
+pub fn draw(ctx: &Context, settings: &mut Settings) {
+    self_health::draw(ctx, settings);
+
+    #[cfg(feature = "feature-esp")]
+    esp::draw(ctx, settings);
+
+    #[cfg(feature = "feature-radar")]
+    radar::draw(ctx, settings);
+
+    #[cfg(feature = "feature-aimbot")]
+    crate::core::aimbot::draw_fov_circle(ctx, settings);
+}
+
+/// Returns the color for a health value, fading from red at 0 to green at 100.
+#[must_use]
+pub fn health_color(health: i32) -> Color32 {
+    let fraction = health.clamp(0, 100) as f32 / 100.0;
+
+    Color32::from_rgb(((1.0 - fraction) * 255.0) as u8, (fraction * 255.0) as u8, 0)
+}
@@ -0,0 +1,451 @@
+use egui::{
+    Align2, Color32, Context, FontId, Frame, Painter, Pos2, Response, Sense, Shape, Stroke, Ui,
+    Vec2, Window,
+};
+
+use crate::{
+    common::Mutex,
+    core::settings::{FeatureId, RadarSettings, Settings},
+    cs2::{
+        entities::{EntityIdentity, EntitySystem},
+        interfaces,
+    },
+};
+
+/// Width and height of the radar, in points.
+const RADAR_SIZE: f32 = 200.0;
+
+/// World units from the center to the edge of the radar at a zoom of `1.0`.
+const BASE_RANGE: f32 = 2000.0;
+
+/// How fast scrolling changes the zoom. One notch of a mouse wheel scrolls about 50 points.
+const ZOOM_SPEED: f32 = 0.004;
+
+/// Radius of a player dot, in points.
+const DOT_RADIUS: f32 = 3.5;
+
+/// Distance from the center of the local player's arrow to its tip, in points.
+const LOCAL_ARROW_SIZE: f32 = 5.0;
+
+/// Size of the label next to a player dot.
+const LABEL_FONT_SIZE: f32 = 9.0;
+
+/// Fraction of the radar radius from which labels start fading out towards the edge.
+const LABEL_FADE_START: f32 = 0.7;
+
+/// Opacity of a label on the edge of the radar.
+const LABEL_MIN_ALPHA: f32 = 0.15;
+
+/// Radius of a grenade dot, in points.
+const GRENADE_RADIUS: f32 = 2.5;
+
+/// Downward acceleration of a thrown grenade: `sv_gravity` scaled by the grenade gravity factor.
+const GRENADE_GRAVITY: f32 = 800.0 * 0.4;
+
+/// Time step of the landing prediction, in seconds.
+const PREDICTION_STEP: f32 = 0.05;
+
+/// Longest flight the landing prediction simulates, in seconds.
+const MAX_PREDICTION_TIME: f32 = 3.0;
+
+/// Grenades slower than this, in units per second, are considered to have landed.
+const MIN_GRENADE_SPEED: f32 = 1.0;
+
+/// Where the radar window was in the previous frame, or `None` before it was first shown. If
+/// `RadarSettings::position` no longer matches it, the setting was changed outside the radar, for
+/// example by loading a profile.
+static LAST_POSITION: Mutex<Option<Pos2>> = Mutex::new(None);
+
+/// A kind of thrown grenade shown on the radar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GrenadeKind {
+    He,
+    Flashbang,
+    Smoke,
+    Molotov,
+    Decoy,
+}
+
+impl GrenadeKind {
+    /// Returns the kind of a grenade projectile from its designer name, or `None` if the entity
+    /// is not a grenade projectile.
+    #[must_use]
+    pub fn from_designer_name(name: &str) -> Option<Self> {
+        match name {
+            "hegrenade_projectile" => Some(Self::He),
+            "flashbang_projectile" => Some(Self::Flashbang),
+            "smokegrenade_projectile" => Some(Self::Smoke),
+            // Incendiary grenades use the molotov projectile as well
+            "molotov_projectile" => Some(Self::Molotov),
+            "decoy_projectile" => Some(Self::Decoy),
+            _ => None,
+        }
+    }
+
+    /// Returns the color of the grenade's radar dot.
+    #[must_use]
+    pub const fn color(self) -> Color32 {
+        match self {
+            Self::He => Color32::RED,
+            Self::Flashbang => Color32::WHITE,
+            Self::Smoke => Color32::GRAY,
+            Self::Molotov => Color32::from_rgb(255, 140, 0),
+            Self::Decoy => Color32::YELLOW,
+        }
+    }
+
+    /// Checks whether grenades of this kind are shown with the given radar settings.
+    #[must_use]
+    pub const fn is_shown(self, radar: &RadarSettings) -> bool {
+        radar.show_grenades
+            && match self {
+                Self::He => radar.show_he_grenades,
+                Self::Flashbang => radar.show_flashbangs,
+                Self::Smoke => radar.show_smokes,
+                Self::Molotov => radar.show_molotovs,
+                Self::Decoy => radar.show_decoys,
+            }
+    }
+}
+
+/// Maps world positions onto the radar for one frame.
+struct RadarView {
+    center: Pos2,
+    radius: f32,
+    range: f32,
+    local_origin: [f32; 3],
+    yaw: f32,
+    opacity: f32,
+}
+
+impl RadarView {
+    /// Returns the offset of a world position from the center of the radar.
+    fn offset(&self, origin: [f32; 3]) -> Vec2 {
+        project(self.local_origin, self.yaw, origin, self.range, self.radius)
+    }
+
+    /// Returns the position of a world position on the screen.
+    fn to_screen(&self, origin: [f32; 3]) -> Pos2 {
+        self.center + self.offset(origin)
+    }
+
+    /// Applies the radar's opacity to a color.
+    fn fade(&self, color: Color32) -> Color32 {
+        color.gamma_multiply(self.opacity)
+    }
+}
+
+/// Draws the radar window with a dot for every living player around the local player.
+///
+/// The radar is rotated so the local player's view direction points up, shown by the arrow in its
+/// center. Every dot is labeled with the player's name, or with a glyph for the class of their
+/// weapon if names are disabled. Thrown grenades are shown as smaller dots colored by type, with a
+/// dashed line to where they are predicted to land. Scrolling while the cursor is over the radar
+/// zooms in and out; double-clicking resets the zoom. Where the window is dragged to is kept in
+/// `RadarSettings::position`, and the window follows the setting when it is changed elsewhere.
+///
+/// # Parameters
+///
+/// * `ctx`: A reference to the `Context` used for drawing.
+/// * `settings`: A mutable reference to the current settings, as the zoom and position are changed
+///   from the radar itself.
+pub fn draw(ctx: &Context, settings: &mut Settings) {
+    if !settings.features_enabled.contains(&FeatureId::Radar) || !settings.visuals.radar.enabled {
+        return;
+    }
+
+    let Some(entity_system) = interfaces::game_resource_service().entity_system() else {
+        return;
+    };
+
+    let Some(local) = entity_system.local_player_pawn() else {
+        return;
+    };
+
+    let Some(local_origin) = local.origin() else {
+        return;
+    };
+
+    let yaw = local.eye_yaw().unwrap_or_default().to_radians();
+    let local_team = local.team();
+    let radar = &mut settings.visuals.radar;
+
+    let opacity = radar.opacity.clamp(RadarSettings::MIN_OPACITY, 1.0);
+    let frame = Frame::window(&ctx.style()).multiply_with_opacity(opacity);
+
+    let mut last_position = LAST_POSITION.lock();
+
+    let mut window = Window::new("radar").frame(frame).resizable(false).collapsible(false);
+
+    // egui remembers where the window was dragged to, so only move it when the setting changed
+    if *last_position != Some(radar.position) {
+        window = window.current_pos(radar.position);
+    }
+
+    let response = window.show(ctx, |ui| {
+        let (response, painter) = ui.allocate_painter(Vec2::splat(RADAR_SIZE), Sense::click());
+
+        handle_zoom(ui, &response, radar);
+
+        let center = response.rect.center();
+        let radius = RADAR_SIZE / 2.0;
+
+        let view = RadarView {
+            center,
+            radius,
+            range: BASE_RANGE / radar.zoom,
+            local_origin,
+            yaw,
+            opacity,
+        };
+
+        painter.circle(
+            center,
+            radius,
+            view.fade(Color32::from_black_alpha(160)),
+            Stroke::new(1.0, view.fade(Color32::GRAY)),
+        );
+        painter.line_segment(
+            [center - Vec2::new(radius, 0.0), center + Vec2::new(radius, 0.0)],
+            Stroke::new(1.0, view.fade(Color32::from_gray(60))),
+        );
+        painter.line_segment(
+            [center - Vec2::new(0.0, radius), center + Vec2::new(0.0, radius)],
+            Stroke::new(1.0, view.fade(Color32::from_gray(60))),
+        );
+
+        draw_grenades(&painter, &view, entity_system, radar);
+
+        for (controller, pawn) in entity_system.players() {
+            if pawn.entity == local.entity || pawn.health().is_none_or(|health| health <= 0) {
+                continue;
+            }
+
+            let Some(origin) = pawn.origin() else {
+                continue;
+            };
+
+            let offset = view.offset(origin);
+            let position = center + offset;
+            let color = if pawn.team() == local_team { Color32::GREEN } else { Color32::RED };
+
+            painter.circle_filled(position, DOT_RADIUS, view.fade(color));
+
+            let label = if radar.show_names {
+                controller.player_name()
+            } else {
+                pawn.active_weapon().and_then(weapon_glyph).map(str::to_owned)
+            };
+
+            if let Some(label) = label {
+                let alpha = label_alpha(offset.length() / radius) * opacity;
+
+                draw_label(&painter, position, &label, alpha);
+            }
+        }
+
+        draw_local_arrow(&painter, center, view.fade(Color32::WHITE));
+    });
+
+    if let Some(response) = response {
+        radar.position = response.response.rect.min;
+        *last_position = Some(radar.position);
+    }
+}
+
+/// Draws the local player as an arrow pointing up, the direction they are looking in.
+fn draw_local_arrow(painter: &Painter, center: Pos2, color: Color32) {
+    let points = vec![
+        center + Vec2::new(0.0, -LOCAL_ARROW_SIZE),
+        center + Vec2::new(LOCAL_ARROW_SIZE * 0.8, LOCAL_ARROW_SIZE),
+        center + Vec2::new(-LOCAL_ARROW_SIZE * 0.8, LOCAL_ARROW_SIZE),
+    ];
+
+    painter.add(Shape::convex_polygon(points, color, Stroke::NONE));
+}
+
+/// Draws every thrown grenade that is enabled in `radar`, with its predicted landing path.
+fn draw_grenades(
+    painter: &Painter,
+    view: &RadarView,
+    entity_system: &EntitySystem,
+    radar: &RadarSettings,
+) {
+    if !radar.show_grenades {
+        return;
+    }
+
+    for (_, grenade) in entity_system.entities() {
+        let Some(kind) = grenade.designer_name().and_then(GrenadeKind::from_designer_name) else {
+            continue;
+        };
+
+        if !kind.is_shown(radar) {
+            continue;
+        }
+
+        let Some(origin) = grenade.origin() else {
+            continue;
+        };
+
+        let color = view.fade(kind.color());
+        let velocity = grenade.velocity().unwrap_or_default();
+
+        if Vec2::new(velocity[0], velocity[1]).length() >= MIN_GRENADE_SPEED {
+            // The local player's height stands in for the ground, which is not known
+            let path: Vec<Pos2> = predict_path(origin, velocity, view.local_origin[2])
+                .into_iter()
+                .map(|point| view.to_screen(point))
+                .collect();
+
+            if let Some(&landing) = path.last() {
+                painter.extend(Shape::dashed_line(&path, Stroke::new(1.0, color), 4.0, 3.0));
+                painter.circle_stroke(landing, GRENADE_RADIUS, Stroke::new(1.0, color));
+            }
+        }
+
+        painter.circle_filled(view.to_screen(origin), GRENADE_RADIUS, color);
+    }
+}
+
+/// Predicts the flight path of a grenade until it falls to `ground_z`.
+///
+/// Bounces and collisions with the map are not simulated, so the prediction is only accurate for
+/// grenades with a clear flight path.
+///
+/// # Parameters
+///
+/// * `origin`: The current position of the grenade.
+/// * `velocity`: The current velocity of the grenade, in units per second.
+/// * `ground_z`: The height at which the grenade is considered to have landed.
+///
+/// # Returns
+///
+/// The positions of the grenade at every `PREDICTION_STEP`, starting with `origin` and ending with
+/// the predicted landing point.
+#[must_use]
+pub fn predict_path(origin: [f32; 3], velocity: [f32; 3], ground_z: f32) -> Vec<[f32; 3]> {
+    let mut position = origin;
+    let mut velocity = velocity;
+    let mut path = vec![position];
+    let mut time = 0.0;
+
+    while time < MAX_PREDICTION_TIME && !(position[2] <= ground_z && velocity[2] <= 0.0) {
+        velocity[2] -= GRENADE_GRAVITY * PREDICTION_STEP;
+
+        for (axis, speed) in position.iter_mut().zip(velocity) {
+            *axis += speed * PREDICTION_STEP;
+        }
+
+        path.push(position);
+        time += PREDICTION_STEP;
+    }
+
+    path
+}
+
+/// Draws `label` to the right of the dot at `position`.
+fn draw_label(painter: &Painter, position: Pos2, label: &str, alpha: f32) {
+    painter.text(
+        position + Vec2::new(DOT_RADIUS + 2.0, 0.0),
+        Align2::LEFT_CENTER,
+        label,
+        FontId::proportional(LABEL_FONT_SIZE),
+        Color32::WHITE.gamma_multiply(alpha),
+    );
+}
+
+/// Returns the opacity of a label, which fades out as the dot approaches the edge of the radar,
+/// where clamped dots pile up.
+///
+/// # Parameters
+///
+/// * `distance`: The distance of the dot from the center, as a fraction of the radar radius.
+#[must_use]
+pub fn label_alpha(distance: f32) -> f32 {
+    let fade = ((distance - LABEL_FADE_START) / (1.0 - LABEL_FADE_START)).clamp(0.0, 1.0);
+
+    1.0 - fade * (1.0 - LABEL_MIN_ALPHA)
+}
+
+/// Returns a short glyph for the class of a weapon, such as `R` for rifles.
+///
+/// # Returns
+///
+/// `None` if the weapon has no designer name.
+fn weapon_glyph(weapon: &EntityIdentity) -> Option<&'static str> {
+    let name = weapon.designer_name()?.strip_prefix("weapon_").unwrap_or_default();
+
+    let glyph = match name {
+        "awp" | "ssg08" | "scar20" | "g3sg1" => "S",
+        "ak47" | "m4a1" | "m4a1_silencer" | "aug" | "sg556" | "famas" | "galilar" => "R",
+        "mac10" | "mp5sd" | "mp7" | "mp9" | "p90" | "bizon" | "ump45" => "M",
+        "nova" | "xm1014" | "mag7" | "sawedoff" | "m249" | "negev" => "H",
+        "glock" | "hkp2000" | "usp_silencer" | "p250" | "fiveseven" | "tec9" | "cz75a"
+        | "deagle" | "revolver" | "elite" => "P",
+        "hegrenade" | "flashbang" | "smokegrenade" | "molotov" | "incgrenade" | "decoy" => "G",
+        "c4" => "B",
+        name if name.starts_with("knife") || name == "bayonet" => "K",
+        _ => "?",
+    };
+
+    Some(glyph)
+}
+
+/// Zooms the radar with the scroll wheel while the cursor is over it, and resets the zoom on a
+/// double-click.
+fn handle_zoom(ui: &Ui, response: &Response, radar: &mut RadarSettings) {
+    if response.double_clicked() {
+        radar.zoom = RadarSettings::DEFAULT_ZOOM;
+    }
+
+    if !response.hovered() {
+        return;
+    }
+
+    let scroll = ui.input(|input| input.scroll_delta.y);
+
+    if scroll != 0.0 {
+        radar.zoom = (radar.zoom * (scroll * ZOOM_SPEED).exp())
+            .clamp(RadarSettings::MIN_ZOOM, RadarSettings::MAX_ZOOM);
+    }
+}
+
+/// Projects a world position onto the radar.
+///
+/// # Parameters
+///
+/// * `local_origin`: The position of the local player, shown at the center of the radar.
+/// * `yaw`: The local player's view yaw in radians, which points up on the radar.
+/// * `origin`: The world position to project.
+/// * `range`: World units from the center to the edge of the radar.
+/// * `radius`: The radius of the radar, in points.
+///
+/// # Returns
+///
+/// The offset from the center of the radar, in points. Positions out of range are clamped to the
+/// edge of the radar.
+#[must_use]
+pub fn project(
+    local_origin: [f32; 3],
+    yaw: f32,
+    origin: [f32; 3],
+    range: f32,
+    radius: f32,
+) -> Vec2 {
+    let dx = origin[0] - local_origin[0];
+    let dy = origin[1] - local_origin[1];
+
+    // Rotate into view space: `forward` along the view direction, `right` to its right
+    let (sin, cos) = yaw.sin_cos();
+    let forward = dx * cos + dy * sin;
+    let right = dx * sin - dy * cos;
+
+    let offset = Vec2::new(right, -forward) * (radius / range);
+
+    if offset.length() > radius {
+        offset.normalized() * radius
+    } else {
+        offset
+    }
+}
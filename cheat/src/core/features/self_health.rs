@@ -0,0 +1,62 @@
+use egui::{Align2, Color32, Context, FontId, LayerId, Pos2};
+
+use crate::{
+    core::settings::{FeatureId, Settings},
+    cs2::interfaces,
+};
+
+use super::health_color;
+
+/// Distance of the labels from the bottom-left corner of the screen.
+const MARGIN: f32 = 16.0;
+
+/// Size of the health label. The armor label is drawn slightly smaller.
+const FONT_SIZE: f32 = 18.0;
+
+/// Draws the local player's health, with their armor below it, in the bottom-left corner.
+///
+/// Useful for players who hide the native HUD.
+///
+/// # Parameters
+///
+/// * `ctx`: A reference to the `Context` used for drawing.
+/// * `settings`: A reference to the current settings, used to check if the label is enabled.
+pub fn draw(ctx: &Context, settings: &Settings) {
+    if !settings.features_enabled.contains(&FeatureId::SelfHealth)
+        || !settings.visuals.draw_self_health
+    {
+        return;
+    }
+
+    let Some(pawn) = interfaces::game_resource_service()
+        .entity_system()
+        .and_then(|entity_system| entity_system.local_player_pawn())
+    else {
+        return;
+    };
+
+    let Some(health) = pawn.health().filter(|&health| health > 0) else {
+        return;
+    };
+
+    let painter = ctx.layer_painter(LayerId::background());
+    let bottom_left = Pos2::new(MARGIN, ctx.screen_rect().bottom() - MARGIN);
+    let armor_font = FontId::proportional(FONT_SIZE * 0.8);
+
+    // Anchored to the bottom, so the armor label goes first and health is stacked above it
+    let armor_rect = painter.text(
+        bottom_left,
+        Align2::LEFT_BOTTOM,
+        format!("armor {}", pawn.armor().unwrap_or(0)),
+        armor_font,
+        Color32::LIGHT_BLUE,
+    );
+
+    painter.text(
+        Pos2::new(bottom_left.x, armor_rect.top()),
+        Align2::LEFT_BOTTOM,
+        format!("hp {health}"),
+        FontId::proportional(FONT_SIZE),
+        health_color(health),
+    );
+}
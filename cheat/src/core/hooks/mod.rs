@@ -1,17 +1,57 @@
 use crate::{
-    create_hook,
-    cs2::{self},
-    get_original_fn,
-    utils::{self, hook_system, render},
+    common::{c_void, Mutex, OnceLock},
+    core::{crash, settings::SETTINGS},
+    cs2::{self, offsets},
+    utils::{self, create_hook, get_original_fn, render},
 };
 
-use anyhow::{bail, Context};
+#[cfg(any(
+    feature = "feature-aimbot",
+    feature = "feature-triggerbot",
+    feature = "feature-bhop",
+    feature = "feature-no-recoil",
+    feature = "feature-auto-strafe",
+    feature = "feature-jump-throw"
+))]
+use crate::core::settings::FeatureId;
+
+use anyhow::{bail, ensure, Context};
+use std::time::{Duration, Instant};
 
 use windows::{
     core::HRESULT,
-    Win32::Graphics::Dxgi::{Common::DXGI_FORMAT, IDXGISwapChain},
+    Win32::Graphics::Dxgi::{Common::DXGI_FORMAT, IDXGISwapChain, DXGI_PRESENT_PARAMETERS},
 };
 
+/// Minimum time between building two overlay frames, capping overlay updates at roughly 250 Hz.
+const MIN_OVERLAY_FRAME_TIME: Duration = Duration::from_millis(4);
+
+/// The time the last overlay frame was built.
+static LAST_OVERLAY_FRAME: OnceLock<Mutex<Instant>> = OnceLock::new();
+
+/// Renders the overlay onto the swap chain.
+///
+/// A new overlay frame is only built if the previous one was built at least
+/// `MIN_OVERLAY_FRAME_TIME` ago. Presents in between draw the previous frame again, so the overlay
+/// does not flicker at high frame rates.
+fn render_overlay(swapchain: &IDXGISwapChain) {
+    cs2::view_matrix::refresh();
+    render::dx11::check_fullscreen_state(swapchain);
+
+    let mut last_frame = LAST_OVERLAY_FRAME.get_or_init(|| Mutex::new(Instant::now())).lock();
+
+    if last_frame.elapsed() < MIN_OVERLAY_FRAME_TIME && render::dx11::DX11.get().is_some() {
+        drop(last_frame);
+        render::dx11::repaint(swapchain);
+        return;
+    }
+
+    *last_frame = Instant::now();
+    drop(last_frame);
+
+    render::dx11::init_from_swapchain(swapchain);
+}
+
 extern "system" fn hk_present(
     swapchain: IDXGISwapChain,
     sync_interval: u32,
@@ -19,11 +59,31 @@ extern "system" fn hk_present(
 ) -> HRESULT {
     get_original_fn!(hk_present, original_fn, (IDXGISwapChain, u32, u32), HRESULT);
 
-    render::dx11::init_from_swapchain(&swapchain);
+    crash::catch_panic("overlay", || render_overlay(&swapchain));
 
     original_fn(swapchain, sync_interval, flags)
 }
 
+/// `IDXGISwapChain1::Present1` is called on an `IDXGISwapChain1`, which extends `IDXGISwapChain`
+/// through single inheritance, so the interface pointer is also a valid `IDXGISwapChain`.
+extern "system" fn hk_present1(
+    swapchain: IDXGISwapChain,
+    sync_interval: u32,
+    flags: u32,
+    present_parameters: *const DXGI_PRESENT_PARAMETERS,
+) -> HRESULT {
+    get_original_fn!(
+        hk_present1,
+        original_fn,
+        (IDXGISwapChain, u32, u32, *const DXGI_PRESENT_PARAMETERS),
+        HRESULT
+    );
+
+    crash::catch_panic("overlay", || render_overlay(&swapchain));
+
+    original_fn(swapchain, sync_interval, flags, present_parameters)
+}
+
 extern "system" fn hk_resize_buffers(
     swapchain: IDXGISwapChain,
     buffer_count: u32,
@@ -39,70 +99,204 @@ extern "system" fn hk_resize_buffers(
         HRESULT
     );
 
-    let mut renderer = render::dx11::DX11
-        .get()
-        .expect("dx11 renderer is not initialized while resizing buffers")
-        .lock();
+    // Creating the renderer is retried on every present, so the game may resize its buffers
+    // before it succeeds
+    let Some(renderer) = render::dx11::DX11.get() else {
+        return original_fn(swapchain, buffer_count, width, height, new_format, swapchain_flags);
+    };
+
+    // Recreating the render target can fail or panic after the original call already resized the
+    // buffers, so keep its result to return either way
+    let mut original_result = None;
+
+    crash::catch_panic("overlay resize", || {
+        let mut renderer = renderer.lock();
+
+        let resized = renderer.resize_buffers(&swapchain, || {
+            *original_result.insert(original_fn(
+                swapchain.clone(),
+                buffer_count,
+                width,
+                height,
+                new_format,
+                swapchain_flags,
+            ))
+        });
 
-    renderer
-        .resize_buffers(&swapchain, || {
-            original_fn(swapchain.clone(), buffer_count, width, height, new_format, swapchain_flags)
-        })
-        .expect("could not resize buffers")
+        match resized {
+            Err(e) => tracing::error!("failed to recreate the overlay render target: {e}"),
+            // `DXGI_FORMAT_UNKNOWN` keeps the current format, so read it back from the swap chain.
+            Ok(_) => {
+                if let Some(format) =
+                    render::dx11::apply_back_buffer_format(&swapchain, &mut renderer)
+                {
+                    tracing::debug!(
+                        "back buffer resized to {width}x{height}, format: {format:?} (hdr: {})",
+                        render::dx11::is_hdr_format(format)
+                    );
+                }
+            }
+        }
+    });
+
+    original_result.unwrap_or_else(|| {
+        original_fn(swapchain, buffer_count, width, height, new_format, swapchain_flags)
+    })
 }
 
+/// `CCSGOInput::CreateMove` is a member function, so `input` is the game's `CCSGOInput`.
+///
+/// Only `input` is typed. The remaining parameters are expected to hold the split-screen slot, the
+/// active flag and the `CUserCmd`, but their order and types have not been reversed from the
+/// current `client.dll`. Until they are, they are kept as raw register-sized values and passed
+/// through unchanged. Dropping or narrowing any of them would hand the original function garbage
+/// in the registers it still reads, so features have to go through `input` rather than the
+/// command.
 unsafe extern "system" fn hk_create_move(
-    a1: *mut f32,
+    input: *mut c_void,
     a2: u64,
     a3: i8,
     a4: u64,
     a5: u64,
     a6: u64,
 ) -> u64 {
-    get_original_fn!(hk_create_move, original_fn, (*mut f32, u64, i8, u64, u64, u64), u64);
+    get_original_fn!(hk_create_move, original_fn, (*mut c_void, u64, i8, u64, u64, u64), u64);
+
+    crash::catch_panic("create move features", || {
+        // The menu holds the settings while it is drawn, so skip the features for this command
+        // instead of stalling the game thread. Each feature gets a copy of its settings, or `None`
+        // if it is disabled in `Settings::features_enabled`.
+        let Some(settings) = SETTINGS.try_lock() else {
+            return;
+        };
+
+        #[cfg(feature = "feature-aimbot")]
+        let aimbot =
+            settings.features_enabled.contains(&FeatureId::Aimbot).then_some(settings.aimbot);
+        #[cfg(feature = "feature-triggerbot")]
+        let triggerbot = settings
+            .features_enabled
+            .contains(&FeatureId::Triggerbot)
+            .then_some(settings.triggerbot);
+        #[cfg(feature = "feature-bhop")]
+        let bhop = settings.features_enabled.contains(&FeatureId::Bhop).then_some(settings.misc);
+        #[cfg(feature = "feature-no-recoil")]
+        let no_recoil =
+            settings.features_enabled.contains(&FeatureId::NoRecoil).then_some(settings.misc);
+        #[cfg(feature = "feature-auto-strafe")]
+        let auto_strafe =
+            settings.features_enabled.contains(&FeatureId::AutoStrafe).then_some(settings.misc);
+        #[cfg(feature = "feature-jump-throw")]
+        let jump_throw =
+            settings.features_enabled.contains(&FeatureId::JumpThrow).then_some(settings.misc);
+
+        drop(settings);
+
+        #[cfg(feature = "feature-aimbot")]
+        crate::core::aimbot::run(input, aimbot.as_ref());
+
+        #[cfg(feature = "feature-triggerbot")]
+        crate::core::triggerbot::run(input, triggerbot.as_ref());
 
-    tracing::info!("create move called");
+        #[cfg(feature = "feature-bhop")]
+        crate::core::bhop::run(input, bhop.as_ref());
 
-    original_fn(a1, a2, a3, a4, a5, a6)
+        #[cfg(feature = "feature-no-recoil")]
+        crate::core::no_recoil::run(input, no_recoil.as_ref());
+        #[cfg(feature = "feature-auto-strafe")]
+        crate::core::auto_strafe::run(input, auto_strafe.as_ref());
+        #[cfg(feature = "feature-jump-throw")]
+        crate::core::misc::jump_throw(input, jump_throw.as_ref());
+    });
+
+    original_fn(input, a2, a3, a4, a5, a6)
 }
 
 /// Initializes hooks for various game functions.
 ///
 /// This function initializes `MinHook` and sets up hooks for the following game functions:
-/// - `hk_create_move`: A hook for the game's create move function.
 /// - `hk_present`: A hook for the game's present function.
 /// - `hk_resize_buffers`: A hook for the game's resize buffers function.
+/// - `hk_present1`: A hook for the game's present1 function, if the overlay routes it separately.
+/// - `hk_create_move`: A hook for the game's create move function.
+///
+/// The targets are looked up in the offset registry, which `cs2::offsets::initialize` fills in
+/// beforehand. Only the optional present1 target is scanned for here. All hooks are created first
+/// and then enabled together. If the create move offset is missing, the overlay hooks are still
+/// enabled before the error is returned, so the overlay shows it.
 ///
 /// # Errors
 ///
 /// If `MinHook` fails to initialize, an error is returned with a message indicating the failure.
 pub fn initialize_hooks() -> anyhow::Result<()> {
+    let _span = tracing::info_span!("initialize_hooks").entered();
+
     // Initialize MinHook
-    if let Err(status) = utils::hook_system::initialize_minhook() {
-        bail!("failed to initialize MinHook: {status}");
+    utils::hook_system::initialize_minhook().context("failed to initialize MinHook")?;
+
+    let client = cs2::modules::client();
+    let overlay = cs2::modules::gameoverlayrenderer64();
+
+    let present_target = offsets::get("present")?;
+    let resize_buffers_target = offsets::get("resize_buffers")?;
+
+    // Present1 is only routed separately by some overlay versions, so it is optional
+    let present1_target = offsets::scan(
+        overlay,
+        "present1",
+        "48 89 5C 24 ?? 48 89 6C 24 ?? 48 89 74 24 ?? 57 41 54 41 55 41 56 41 57 48 83 EC 30 4D 8B F1",
+    )
+        .inspect_err(|e| tracing::warn!("present1 pattern not found, skipping hook: {e}"))
+        .ok()
+        .filter(|&target| {
+            let contained = overlay.contains(target);
+
+            if !contained {
+                tracing::warn!(
+                    "present1 target {target:#x} is outside of {}, skipping hook",
+                    overlay.name()
+                );
+            }
+
+            contained
+        });
+
+    // A match outside of the module means the pattern matched unrelated memory
+    for (name, target) in [("present", present_target), ("resize buffers", resize_buffers_target)] {
+        ensure!(
+            overlay.contains(target),
+            "{name} target {target:#x} is outside of {}",
+            overlay.name()
+        );
     }
 
-    // Find the target addresses for the game functions
-    let create_move_target = cs2::modules::client()
-        .find_seq_of_bytes("48 8B C4 4C 89 48 20 55")
-        .context("failed to find create move pattern")?;
-
-    let present_target = cs2::modules::gameoverlayrenderer64()
-        .find_seq_of_bytes(
-            "48 89 5C 24 ?? 48 89 6C 24 ?? 48 89 74 24 ?? 57 41 56 41 57 48 83 EC 20 41 8B E8",
-        )
-        .context("failed to find present pattern")?;
-
-    let resize_buffers_target = cs2::modules::gameoverlayrenderer64()
-        .find_seq_of_bytes(
-            "48 89 5C 24 08 48 89 6C 24 10 48 89 74 24 18 57 41 56 41 57 48 83 EC 30 44",
-        )
-        .context("failed to find resize buffers pattern")?;
-
-    // Create hooks for the game functions
-    create_hook!(create_move_target, hk_create_move);
+    // A missing create move target is returned after the overlay hooks are enabled, so the
+    // overlay can report it
+    let create_move_target = offsets::get("create_move").and_then(|target| {
+        ensure!(
+            client.contains(target),
+            "create move target {target:#x} is outside of {}",
+            client.name()
+        );
+
+        Ok(target)
+    });
+
+    // Create hooks for the overlay functions
     create_hook!(present_target, hk_present);
     create_hook!(resize_buffers_target, hk_resize_buffers);
 
-    Ok(())
+    if let Some(present1_target) = present1_target {
+        tracing::debug!("found present1 at {present1_target:#x}");
+
+        create_hook!(present1_target, hk_present1);
+    }
+
+    if let Ok(create_move_target) = create_move_target {
+        create_hook!(create_move_target, hk_create_move);
+    }
+
+    utils::hook_system::enable_all_hooks().context("failed to enable hooks")?;
+
+    create_move_target.map(|_| ())
 }
@@ -0,0 +1,57 @@
+use crate::{
+    common::{c_void, Mutex},
+    core::settings::MiscSettings,
+    cs2::input::{CCSGOInput, IN_ATTACK, IN_JUMP},
+    utils::render::win32::is_key_down,
+};
+
+/// Progress of the jump-throw bind, carried from one command to the next.
+static JUMP_THROW: Mutex<JumpThrowState> =
+    Mutex::new(JumpThrowState { key_was_down: false, release_pending: false });
+
+/// State of the jump-throw bind.
+struct JumpThrowState {
+    /// Whether the key was held in the previous command, so a held key throws only once.
+    key_was_down: bool,
+
+    /// Whether the previous command jumped with the attack held, so this one has to release it.
+    release_pending: bool,
+}
+
+/// Throws the held grenade at the top of a jump when `MiscSettings::jump_throw_key` is pressed.
+/// Called from `hk_create_move` before the game builds the command.
+///
+/// The command of the press jumps with the attack held, and the next command releases both, so
+/// the throw always leaves on the tick after the jump regardless of the frame rate.
+///
+/// # Parameters
+///
+/// * `input`: The game's `CCSGOInput`, as passed to `CreateMove`.
+/// * `misc`: The misc settings for this command, or `None` if `FeatureId::JumpThrow` is disabled.
+pub fn jump_throw(input: *mut c_void, misc: Option<&MiscSettings>) {
+    let key = misc.map_or(0, |misc| misc.jump_throw_key);
+    let mut state = JUMP_THROW.lock();
+    let key_down = key != 0 && is_key_down(key);
+    let pressed = key_down && !state.key_was_down;
+    state.key_was_down = key_down;
+
+    if !pressed && !state.release_pending {
+        return;
+    }
+
+    let Some(input) = CCSGOInput::from_ptr(input) else {
+        return;
+    };
+
+    let Some(buttons) = input.buttons() else {
+        return;
+    };
+
+    if state.release_pending {
+        input.set_buttons(buttons & !(IN_JUMP | IN_ATTACK));
+        state.release_pending = false;
+    } else {
+        input.set_buttons(buttons | IN_JUMP | IN_ATTACK);
+        state.release_pending = true;
+    }
+}
@@ -1,4 +1,21 @@
+#[cfg(feature = "feature-aimbot")]
+pub mod aimbot;
+#[cfg(feature = "feature-auto-strafe")]
+pub mod auto_strafe;
+#[cfg(feature = "feature-bhop")]
+pub mod bhop;
 pub mod bootstrap;
+pub mod crash;
+pub mod debug;
+pub mod features;
 pub mod hooks;
+#[cfg(feature = "feature-jump-throw")]
+pub mod misc;
+#[cfg(feature = "feature-no-recoil")]
+pub mod no_recoil;
 pub mod settings;
+pub mod shutdown;
+pub mod stats;
+#[cfg(feature = "feature-triggerbot")]
+pub mod triggerbot;
 pub mod ui;
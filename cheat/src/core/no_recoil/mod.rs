@@ -0,0 +1,62 @@
+use crate::{
+    common::{c_void, Mutex},
+    core::settings::MiscSettings,
+    cs2::{input::CCSGOInput, interfaces, math},
+};
+
+/// Ratio between the aim punch and how far it moves the view.
+const PUNCH_SCALE: f32 = 2.0;
+
+/// The pitch and yaw that were subtracted from the view angles for the previous command.
+static LAST_COMPENSATION: Mutex<[f32; 2]> = Mutex::new([0.0; 2]);
+
+/// Counteracts the local player's aim punch, `m_aimPunchAngle * 2 * factor`, in the view angles.
+/// Called from `hk_create_move` before the game builds the command.
+///
+/// The view angles keep whatever was written to them, so only the change in compensation since
+/// the previous command is applied. This also returns the view to where it was once the punch
+/// wears off.
+///
+/// # Parameters
+///
+/// * `input`: The game's `CCSGOInput`, as passed to `CreateMove`.
+/// * `misc`: The misc settings for this command, or `None` if `FeatureId::NoRecoil` is disabled.
+pub fn run(input: *mut c_void, misc: Option<&MiscSettings>) {
+    let enabled = misc.is_some_and(|misc| misc.no_recoil);
+    let factor = misc.map_or(0.0, |misc| misc.no_recoil_factor.clamp(0.0, 1.0));
+
+    let mut last_compensation = LAST_COMPENSATION.lock();
+
+    let compensation = enabled
+        .then(|| {
+            interfaces::game_resource_service()
+                .entity_system()?
+                .local_player_pawn()?
+                .as_player_pawn()?
+                .aim_punch_angle()
+        })
+        .flatten()
+        .map_or([0.0; 2], |[pitch, yaw, _]| {
+            [pitch * PUNCH_SCALE * factor, yaw * PUNCH_SCALE * factor]
+        });
+
+    if compensation == *last_compensation {
+        return;
+    }
+
+    let Some(input) = CCSGOInput::from_ptr(input) else {
+        return;
+    };
+
+    let Some([pitch, yaw, roll]) = input.view_angles() else {
+        return;
+    };
+
+    input.set_view_angles([
+        pitch - (compensation[0] - last_compensation[0]),
+        math::normalize_yaw(yaw - (compensation[1] - last_compensation[1])),
+        roll,
+    ]);
+
+    *last_compensation = compensation;
+}
@@ -1,56 +1,537 @@
+use anyhow::Context;
+#[cfg(feature = "feature-esp")]
 use egui::Color32;
-use lazy_static::lazy_static;
+#[cfg(feature = "feature-radar")]
+use egui::Pos2;
 use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, path::PathBuf, sync::LazyLock, thread, time::Duration};
+use tracing_subscriber::filter::LevelFilter;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_INSERT;
 
-lazy_static! {
-    pub static ref SETTINGS: Mutex<Settings> = Mutex::new(Settings::default());
-}
+#[cfg(feature = "feature-aimbot")]
+use crate::core::aimbot::AimbotSettings;
+#[cfg(feature = "feature-triggerbot")]
+use crate::core::triggerbot::TriggerbotSettings;
+
+pub static SETTINGS: LazyLock<Mutex<Settings>> = LazyLock::new(|| Mutex::new(Settings::default()));
 
-#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Tab {
+    #[default]
     Visuals,
+    #[cfg(any(feature = "feature-aimbot", feature = "feature-triggerbot"))]
+    Aimbot,
     Misc,
 }
 
+/// Identifies a cheat feature that can be toggled at runtime.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FeatureId {
+    Esp,
+    Aimbot,
+    Bhop,
+    Triggerbot,
+    Radar,
+    NoRecoil,
+    AutoStrafe,
+    JumpThrow,
+    SelfHealth,
+}
+
+impl FeatureId {
+    /// Every feature, in the order they are listed in the menu.
+    pub const ALL: [Self; 9] = [
+        Self::Esp,
+        Self::Aimbot,
+        Self::Bhop,
+        Self::Triggerbot,
+        Self::Radar,
+        Self::NoRecoil,
+        Self::AutoStrafe,
+        Self::JumpThrow,
+        Self::SelfHealth,
+    ];
+
+    /// Returns the label shown for the feature in the menu.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Esp => "esp",
+            Self::Aimbot => "aimbot",
+            Self::Bhop => "bhop",
+            Self::Triggerbot => "triggerbot",
+            Self::Radar => "radar",
+            Self::NoRecoil => "no recoil",
+            Self::AutoStrafe => "auto strafe",
+            Self::JumpThrow => "jump throw",
+            Self::SelfHealth => "self health",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Settings {
     pub tab: Tab,
 
+    /// Features that are allowed to run. A feature that is compiled in but missing from this
+    /// set stays idle until it is re-enabled from the menu.
+    pub features_enabled: HashSet<FeatureId>,
+
     pub visuals: VisualsSettings,
+
+    #[cfg(feature = "feature-aimbot")]
+    pub aimbot: AimbotSettings,
+
+    #[cfg(feature = "feature-triggerbot")]
+    pub triggerbot: TriggerbotSettings,
+
     pub misc: MiscSettings,
 }
 
+impl Settings {
+    /// Disables every feature at once.
+    pub fn apply_stealth_preset(&mut self) {
+        self.features_enabled.clear();
+    }
+}
+
 impl Default for Settings {
     fn default() -> Self {
-        Self { tab: Tab::Visuals, visuals: Default::default(), misc: Default::default() }
+        Self {
+            tab: Tab::default(),
+            features_enabled: FeatureId::ALL.into_iter().collect(),
+            visuals: Default::default(),
+            #[cfg(feature = "feature-aimbot")]
+            aimbot: Default::default(),
+            #[cfg(feature = "feature-triggerbot")]
+            triggerbot: Default::default(),
+            misc: Default::default(),
+        }
     }
 }
 
-#[derive(Default)]
+/// Returns the path of the config file, `%APPDATA%\cs2_internal\config.json`.
+///
+/// Falls back to the temporary directory if `APPDATA` is not set.
+#[must_use]
+pub fn config_path() -> PathBuf {
+    std::env::var_os("APPDATA")
+        .map_or_else(std::env::temp_dir, PathBuf::from)
+        .join("cs2_internal")
+        .join("config.json")
+}
+
+/// Loads the config file into `SETTINGS`.
+///
+/// Fields missing from the file keep their default values.
+///
+/// # Errors
+///
+/// Returns an error if the config file could not be read or parsed.
+pub fn load() -> anyhow::Result<()> {
+    let path = config_path();
+    let config = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+
+    *SETTINGS.lock() = serde_json::from_str(&config).context("failed to parse config")?;
+
+    Ok(())
+}
+
+/// Writes `settings` to the config file.
+///
+/// # Errors
+///
+/// Returns an error if the settings could not be serialized or the file could not be written.
+pub fn save(settings: &Settings) -> anyhow::Result<()> {
+    let path = config_path();
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+
+    let config = serde_json::to_string_pretty(settings).context("failed to serialize config")?;
+
+    std::fs::write(&path, config).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Starts a background thread that saves `SETTINGS` to the config file every `interval`.
+///
+/// The file is only written when the settings differ from what was last saved, so an idle menu
+/// does not touch the disk.
+///
+/// # Parameters
+///
+/// * `interval`: The time between two checks for changed settings.
+pub fn auto_save(interval: Duration) {
+    let mut last_saved = SETTINGS.lock().clone();
+
+    thread::spawn(move || loop {
+        thread::sleep(interval);
+
+        let current = SETTINGS.lock().clone();
+
+        if current == last_saved {
+            continue;
+        }
+
+        match save(&current) {
+            Ok(()) => {
+                tracing::debug!("saved config");
+                last_saved = current;
+            }
+            Err(e) => tracing::warn!("failed to save config: {e:#}"),
+        }
+    });
+}
+
+/// A named copy of the settings that can be loaded back from the menu.
+#[derive(Clone)]
+pub struct Profile {
+    pub name: String,
+    pub settings: Settings,
+}
+
+/// Saved profiles, in the order they were created.
+pub static PROFILES: Mutex<Vec<Profile>> = Mutex::new(Vec::new());
+
+/// Copies `settings` into a new profile slot.
+///
+/// # Returns
+///
+/// The name of the new profile.
+pub fn duplicate_profile(settings: &Settings) -> String {
+    let mut profiles = PROFILES.lock();
+    let name = format!("profile {}", profiles.len() + 1);
+
+    profiles.push(Profile { name: name.clone(), settings: settings.clone() });
+
+    name
+}
+
+/// Settings of the visual features.
+///
+/// This and the other per-feature settings structs are `Copy`, so they can be handed to the render
+/// thread without cloning. Heap-allocated fields such as `String` or `Vec` belong in `Settings`
+/// itself rather than in these structs.
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct VisualsSettings {
+    #[cfg(feature = "feature-esp")]
     pub esp: EspSettings,
+
+    #[cfg(feature = "feature-radar")]
+    pub radar: RadarSettings,
+
+    /// Shows the local player's health and armor in the bottom-left corner.
+    pub draw_self_health: bool,
 }
 
+#[cfg(feature = "feature-radar")]
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RadarSettings {
+    pub enabled: bool,
+
+    /// Magnification of the radar, between `RadarSettings::MIN_ZOOM` and `RadarSettings::MAX_ZOOM`.
+    pub zoom: f32,
+
+    /// Top left corner of the radar window on the screen, updated when the window is dragged.
+    pub position: Pos2,
+
+    /// Opacity of the radar, between `RadarSettings::MIN_OPACITY` and 1.
+    pub opacity: f32,
+
+    /// Labels each radar dot with the player's name instead of a glyph for their weapon class.
+    pub show_names: bool,
+
+    /// Shows thrown grenades, filtered by the per-type flags below.
+    pub show_grenades: bool,
+    pub show_he_grenades: bool,
+    pub show_flashbangs: bool,
+    pub show_smokes: bool,
+    pub show_molotovs: bool,
+    pub show_decoys: bool,
+}
+
+#[cfg(feature = "feature-radar")]
+impl RadarSettings {
+    pub const MIN_ZOOM: f32 = 0.1;
+    pub const MAX_ZOOM: f32 = 5.0;
+    pub const DEFAULT_ZOOM: f32 = 1.0;
+    pub const MIN_OPACITY: f32 = 0.2;
+}
+
+#[cfg(feature = "feature-radar")]
+impl Default for RadarSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            zoom: Self::DEFAULT_ZOOM,
+            position: Pos2::new(16.0, 160.0),
+            opacity: 1.0,
+            show_names: true,
+            show_grenades: true,
+            show_he_grenades: true,
+            show_flashbangs: true,
+            show_smokes: true,
+            show_molotovs: true,
+            show_decoys: true,
+        }
+    }
+}
+
+#[cfg(feature = "feature-esp")]
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(from = "EspSettingsConfig")]
 pub struct EspSettings {
     pub enabled: bool,
     pub draw_boxes: bool,
-    pub box_color: Color32,
+
+    /// Draws each player's skeleton in their box color.
+    pub draw_skeleton: bool,
+
+    /// Box color of players on the other team.
+    pub enemy_color: Color32,
+
+    /// Box color of players on the local player's team.
+    pub team_color: Color32,
+
+    /// Draws the ESP for the local player's teammates as well as for enemies.
+    pub show_teammates: bool,
+
+    /// Draws the ESP for dormant players at their last known position. Meant for debugging.
+    pub show_dormant: bool,
+
+    /// Draws the ESP for bots. Off by default, as bots are rarely worth watching and add clutter.
+    pub show_bots: bool,
+
     pub draw_nametags: bool,
+
+    /// Shows the name of the weapon each player holds below their box.
+    pub draw_weapon: bool,
+
     pub draw_money: bool,
     pub draw_health: bool,
+
+    /// Draws a line from the bottom center of the screen to each player's feet.
+    pub draw_snaplines: bool,
+    pub snapline_color: Color32,
 }
 
+#[cfg(feature = "feature-esp")]
 impl Default for EspSettings {
     fn default() -> Self {
         Self {
             enabled: true,
             draw_boxes: true,
-            box_color: Color32::from_rgb(237, 135, 150),
+            draw_skeleton: false,
+            enemy_color: Color32::from_rgb(237, 135, 150),
+            team_color: Color32::from_rgb(166, 218, 149),
+            show_teammates: true,
+            show_dormant: false,
+            show_bots: false,
             draw_nametags: true,
+            draw_weapon: true,
             draw_money: true,
             draw_health: true,
+            draw_snaplines: false,
+            snapline_color: Color32::WHITE,
+        }
+    }
+}
+
+/// The config file layout of `EspSettings`.
+///
+/// Older configs store a single `box_color` instead of `enemy_color` and `team_color`. It is
+/// migrated into `enemy_color` when the config has no `enemy_color` of its own.
+#[cfg(feature = "feature-esp")]
+#[derive(Deserialize)]
+#[serde(default)]
+struct EspSettingsConfig {
+    enabled: bool,
+    draw_boxes: bool,
+    draw_skeleton: bool,
+    #[serde(rename = "box_color")]
+    legacy_box_color: Option<Color32>,
+    enemy_color: Option<Color32>,
+    team_color: Option<Color32>,
+    show_teammates: bool,
+    show_dormant: bool,
+    show_bots: bool,
+    draw_nametags: bool,
+    draw_weapon: bool,
+    draw_money: bool,
+    draw_health: bool,
+    draw_snaplines: bool,
+    snapline_color: Color32,
+}
+
+#[cfg(feature = "feature-esp")]
+impl Default for EspSettingsConfig {
+    fn default() -> Self {
+        let defaults = EspSettings::default();
+
+        Self {
+            enabled: defaults.enabled,
+            draw_boxes: defaults.draw_boxes,
+            draw_skeleton: defaults.draw_skeleton,
+            legacy_box_color: None,
+            enemy_color: None,
+            team_color: None,
+            show_teammates: defaults.show_teammates,
+            show_dormant: defaults.show_dormant,
+            show_bots: defaults.show_bots,
+            draw_nametags: defaults.draw_nametags,
+            draw_weapon: defaults.draw_weapon,
+            draw_money: defaults.draw_money,
+            draw_health: defaults.draw_health,
+            draw_snaplines: defaults.draw_snaplines,
+            snapline_color: defaults.snapline_color,
+        }
+    }
+}
+
+#[cfg(feature = "feature-esp")]
+impl From<EspSettingsConfig> for EspSettings {
+    fn from(config: EspSettingsConfig) -> Self {
+        let defaults = Self::default();
+
+        let (enemy_color, team_color) = match (config.enemy_color, config.legacy_box_color) {
+            (Some(enemy_color), _) => (enemy_color, config.team_color),
+            (None, Some(box_color)) => (box_color, config.team_color.or(Some(Color32::GREEN))),
+            (None, None) => (defaults.enemy_color, config.team_color),
+        };
+
+        Self {
+            enabled: config.enabled,
+            draw_boxes: config.draw_boxes,
+            draw_skeleton: config.draw_skeleton,
+            enemy_color,
+            team_color: team_color.unwrap_or(defaults.team_color),
+            show_teammates: config.show_teammates,
+            show_dormant: config.show_dormant,
+            show_bots: config.show_bots,
+            draw_nametags: config.draw_nametags,
+            draw_weapon: config.draw_weapon,
+            draw_money: config.draw_money,
+            draw_health: config.draw_health,
+            draw_snaplines: config.draw_snaplines,
+            snapline_color: config.snapline_color,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MiscSettings {
+    /// Virtual-key code of the key that shows and hides the menu.
+    pub menu_key: u32,
+
+    pub debug_overlay: bool,
+
+    /// Jumps again on the tick the player lands while jump is held.
+    #[cfg(feature = "feature-bhop")]
+    pub bhop_enabled: bool,
+
+    /// The most verbose log messages that are written.
+    pub log_level: LogLevel,
+
+    /// Counteracts the recoil that weapons apply to the view.
+    #[cfg(feature = "feature-no-recoil")]
+    pub no_recoil: bool,
+
+    /// How much of the recoil `no_recoil` counteracts, from 0 (none) to 1 (all of it).
+    #[cfg(feature = "feature-no-recoil")]
+    pub no_recoil_factor: f32,
+
+    /// Strafes towards the side the mouse turns to while in the air.
+    #[cfg(feature = "feature-auto-strafe")]
+    pub auto_strafe: bool,
+
+    /// Virtual-key code of the key that jumps and throws the held grenade, or 0 for none.
+    #[cfg(feature = "feature-jump-throw")]
+    pub jump_throw_key: u32,
+}
+
+impl Default for MiscSettings {
+    fn default() -> Self {
+        Self {
+            menu_key: u32::from(VK_INSERT.0),
+            debug_overlay: false,
+            #[cfg(feature = "feature-bhop")]
+            bhop_enabled: false,
+            log_level: LogLevel::default(),
+            #[cfg(feature = "feature-no-recoil")]
+            no_recoil: false,
+            #[cfg(feature = "feature-no-recoil")]
+            no_recoil_factor: 1.0,
+            #[cfg(feature = "feature-auto-strafe")]
+            auto_strafe: false,
+            #[cfg(feature = "feature-jump-throw")]
+            jump_throw_key: 0,
+        }
+    }
+}
+
+/// Verbosity of the log output, selectable from the menu.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    #[default]
+    Trace,
+}
+
+impl LogLevel {
+    /// Every level, from the least to the most verbose.
+    pub const ALL: [Self; 6] =
+        [Self::Off, Self::Error, Self::Warn, Self::Info, Self::Debug, Self::Trace];
+
+    /// Returns the label shown for the level in the menu.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "debug",
+            Self::Trace => "trace",
         }
     }
 }
 
-#[derive(Default)]
-pub struct MiscSettings {}
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Off => Self::OFF,
+            LogLevel::Error => Self::ERROR,
+            LogLevel::Warn => Self::WARN,
+            LogLevel::Info => Self::INFO,
+            LogLevel::Debug => Self::DEBUG,
+            LogLevel::Trace => Self::TRACE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FeatureId, Settings};
+
+    #[test]
+    fn stealth_preset_disables_every_feature() {
+        let mut settings = Settings::default();
+        settings.apply_stealth_preset();
+
+        for feature in FeatureId::ALL {
+            assert!(!settings.features_enabled.contains(&feature), "{} is enabled", feature.name());
+        }
+    }
+}
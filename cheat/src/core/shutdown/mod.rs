@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use windows::Win32::{Foundation::HMODULE, System::LibraryLoader::FreeLibraryAndExitThread};
+
+use crate::{
+    common::OnceLock,
+    core::stats,
+    utils::{hook_system, render::win32},
+};
+
+/// How long to wait after removing the hooks for calls already inside them to return.
+const HOOK_DRAIN_DELAY: Duration = Duration::from_millis(100);
+
+/// The handle of the cheat DLL, stored by `dll_main` when the DLL is loaded.
+static MODULE: OnceLock<HMODULE> = OnceLock::new();
+
+/// Stores the handle of the cheat DLL for `unload`.
+///
+/// Only the first handle is kept; later calls do nothing.
+pub fn store_module(module: HMODULE) {
+    _ = MODULE.set(module);
+}
+
+/// Removes every hook, restores the window procedure, logs the session statistics and unloads the
+/// cheat DLL.
+///
+/// This has to run on a thread the cheat created, never inside a hook, because
+/// `FreeLibraryAndExitThread` ends the calling thread.
+///
+/// Failures to remove the hooks or restore the window procedure are logged, and the DLL is not
+/// unloaded in that case, since the game would still call into it. If no module handle was stored
+/// the DLL stays loaded as well.
+pub fn unload() {
+    tracing::info!("unloading cheat...");
+
+    if let Err(e) = hook_system::uninitialize_minhook() {
+        tracing::error!("failed to remove hooks, not unloading: {e:#}");
+        return;
+    }
+
+    if let Err(e) = win32::destroy() {
+        tracing::error!("failed to restore window procedure, not unloading: {e:#}");
+        return;
+    }
+
+    let Some(&module) = MODULE.get() else {
+        tracing::error!("cheat module handle was not stored, cannot unload");
+        return;
+    };
+
+    // Hooks that were entered before they were removed may still be running
+    std::thread::sleep(HOOK_DRAIN_DELAY);
+
+    // `dll_main` runs under the loader lock once the DLL is unloading, where logging is not safe
+    stats::log_session_stats();
+
+    // SAFETY: Hooks and the window procedure no longer point into the cheat, and `module` is the
+    // handle the loader passed to `dll_main`, so this drops the loader's reference and unloads
+    // the DLL. The calling thread is owned by the cheat, as documented above.
+    unsafe { FreeLibraryAndExitThread(module, 0) }
+}
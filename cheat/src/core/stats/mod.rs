@@ -0,0 +1,41 @@
+use crate::common::{Mutex, OnceLock};
+use parking_lot::MutexGuard;
+
+/// Counters collected over the lifetime of the cheat, used to tune feature defaults.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SessionStats {
+    /// Overlay frames that were drawn.
+    pub frames_rendered: u64,
+    /// Entities drawn by the ESP, summed over all frames.
+    pub esp_entities_drawn: u64,
+    /// Times the aimbot locked onto a new target.
+    pub aimbot_targets_locked: u64,
+    /// Shots fired while the aimbot was aiming at a target.
+    pub aimbot_shots_taken: u64,
+}
+
+static SESSION_STATS: OnceLock<Mutex<SessionStats>> = OnceLock::new();
+
+/// Locks the session statistics for reading or updating.
+pub fn session_stats() -> MutexGuard<'static, SessionStats> {
+    SESSION_STATS.get_or_init(|| Mutex::new(SessionStats::default())).lock()
+}
+
+/// Logs the session statistics, for example when the cheat is unloaded.
+///
+/// Nothing is logged if the statistics are locked, since this may run while other threads are
+/// being torn down.
+pub fn log_session_stats() {
+    let Some(stats) = SESSION_STATS.get().and_then(Mutex::try_lock) else {
+        return;
+    };
+
+    tracing::info!(
+        "session stats: {} frames rendered, {} esp entities drawn, {} aimbot targets locked, \
+         {} aimbot shots taken",
+        stats.frames_rendered,
+        stats.esp_entities_drawn,
+        stats.aimbot_targets_locked,
+        stats.aimbot_shots_taken
+    );
+}
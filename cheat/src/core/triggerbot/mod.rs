@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_XBUTTON1;
+
+use crate::{
+    common::{c_void, Mutex},
+    cs2::{
+        input::{CCSGOInput, IN_ATTACK},
+        interfaces,
+    },
+    utils::render::win32::is_key_down,
+};
+
+/// The target under the crosshair, carried from one command to the next.
+static STATE: Mutex<TriggerbotState> = Mutex::new(TriggerbotState { target: None });
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TriggerbotSettings {
+    pub enabled: bool,
+
+    /// Virtual-key code of the key that has to be held for the triggerbot to fire.
+    pub key: u32,
+
+    /// Time a player has to stay under the crosshair before the triggerbot fires, in
+    /// milliseconds.
+    pub delay_ms: u64,
+
+    /// Only fires at players on the other team.
+    pub team_check: bool,
+}
+
+impl TriggerbotSettings {
+    pub const MAX_DELAY_MS: u64 = 500;
+}
+
+impl Default for TriggerbotSettings {
+    fn default() -> Self {
+        Self { enabled: false, key: u32::from(VK_XBUTTON1.0), delay_ms: 50, team_check: true }
+    }
+}
+
+/// Timing state of the triggerbot.
+struct TriggerbotState {
+    /// The entity under the crosshair and when it got there.
+    target: Option<(*const c_void, Instant)>,
+}
+
+// SAFETY: The entity pointer is only compared, never dereferenced.
+unsafe impl Send for TriggerbotState {}
+
+/// Holds the attack button while the triggerbot key is held and a player has been under the
+/// crosshair for `TriggerbotSettings::delay_ms`. Called from `hk_create_move` before the game
+/// builds the command.
+///
+/// # Parameters
+///
+/// * `input`: The game's `CCSGOInput`, as passed to `CreateMove`.
+/// * `triggerbot`: The triggerbot settings for this command, or `None` if
+///   `FeatureId::Triggerbot` is disabled.
+pub fn run(input: *mut c_void, triggerbot: Option<&TriggerbotSettings>) {
+    let mut state = STATE.lock();
+
+    let Some(triggerbot) =
+        triggerbot.filter(|triggerbot| triggerbot.enabled && is_key_down(triggerbot.key))
+    else {
+        state.target = None;
+        return;
+    };
+
+    let Some(target) = target_in_crosshair(triggerbot.team_check) else {
+        state.target = None;
+        return;
+    };
+
+    let since = match state.target {
+        Some((entity, since)) if entity == target => since,
+        _ => state.target.insert((target, Instant::now())).1,
+    };
+
+    if since.elapsed() < Duration::from_millis(triggerbot.delay_ms) {
+        return;
+    }
+
+    let Some(input) = CCSGOInput::from_ptr(input) else {
+        return;
+    };
+
+    if let Some(buttons) = input.buttons() {
+        input.set_buttons(buttons | IN_ATTACK);
+    }
+}
+
+/// Returns the living player under the local player's crosshair, if any.
+///
+/// # Parameters
+///
+/// * `team_check`: Ignores players on the local player's team.
+fn target_in_crosshair(team_check: bool) -> Option<*const c_void> {
+    let entity_system = interfaces::game_resource_service().entity_system()?;
+    let local = entity_system.local_player_pawn()?;
+    let target = local.player_in_crosshair()?.identity()?;
+
+    if !target.is_player_pawn()
+        || target.entity == local.entity
+        || target.health().is_none_or(|health| health <= 0)
+        || (team_check && local.team().is_some() && target.team() == local.team())
+    {
+        return None;
+    }
+
+    Some(target.entity)
+}
@@ -1,10 +1,23 @@
-use crate::core::settings::{Settings, Tab, VisualsSettings};
+use crate::{
+    core::{
+        bootstrap::{self, BootstrapStatus},
+        settings::{self, FeatureId, LogLevel, Settings, Tab, VisualsSettings},
+        stats,
+    },
+    utils::{hotkeys, render::dx11},
+};
 
 #[allow(unused_imports)]
 use egui::{
-    Color32, Context, Pos2, Rect, RichText, ScrollArea, Slider, Stroke, Ui, Widget, Window,
+    Align2, Area, Color32, ComboBox, Context, DragValue, Pos2, Rect, RichText, ScrollArea, Slider,
+    Stroke, Ui, Vec2, Widget, Window,
 };
 
+#[cfg(feature = "feature-aimbot")]
+use crate::core::aimbot::{AimbotBone, AimbotSettings};
+#[cfg(feature = "feature-triggerbot")]
+use crate::core::triggerbot::TriggerbotSettings;
+
 use std::sync::atomic::{AtomicBool, Ordering};
 use windows::Win32::UI::WindowsAndMessaging::{
     WM_CHAR, WM_DEVICECHANGE, WM_KEYDOWN, WM_KEYUP, WM_KILLFOCUS, WM_LBUTTONDBLCLK, WM_LBUTTONDOWN,
@@ -55,20 +68,23 @@ pub fn is_menu_visible() -> bool {
 /// This function checks if the menu is currently visible using the `is_menu_visible` function. If the menu is
 /// not visible, the function returns early without drawing anything. Otherwise, it creates a new window with
 /// the title "enigma euphoria" and displays it using the provided `Context`. The window contains a label
-/// with a contact link, a separator, and two tabs: "visuals" and "misc". Depending on the current tab
-/// selected in the `Settings` struct, the corresponding tab function (`visuals_tab` or `misc_tab`) is
-/// called to draw the specific settings options for that tab.
+/// with a contact link, a separator, and the tabs "visuals", "aimbot" (if compiled in) and "misc".
+/// Depending on the current tab selected in the `Settings` struct, the corresponding tab function
+/// (`visuals_tab`, `aimbot_tab` or `misc_tab`) is called to draw the specific settings options for
+/// that tab.
 ///
 /// # Parameters
 ///
 /// * `ctx`: A reference to the `Context` struct used for drawing UI elements.
 /// * `settings`: A mutable reference to the `Settings` struct containing the current settings and tab
-///               selection.
+///   selection.
 pub fn draw_menu(ctx: &Context, settings: &mut Settings) {
     if !is_menu_visible() {
         return;
     }
 
+    let menu_key = settings.misc.menu_key;
+
     Window::new("enigma euphoria").show(ctx, |ui| {
         ui.label(RichText::new("contact dev: t.me/animstate").color(Color32::WHITE));
         ui.separator();
@@ -77,9 +93,16 @@ pub fn draw_menu(ctx: &Context, settings: &mut Settings) {
 
         match settings.tab {
             Tab::Visuals => visuals_tab(ui, &mut settings.visuals),
-            Tab::Misc => visuals_tab(ui, &mut settings.visuals),
+            #[cfg(any(feature = "feature-aimbot", feature = "feature-triggerbot"))]
+            Tab::Aimbot => aimbot_tab(ui, settings),
+            Tab::Misc => misc_tab(ui, settings),
         }
     });
+
+    // The key also changes when a profile is loaded, so compare after the whole menu is drawn
+    if settings.misc.menu_key != menu_key {
+        hotkeys::HOTKEYS.lock().rebind(menu_key, settings.misc.menu_key);
+    }
 }
 
 fn tabs(ui: &mut Ui, settings: &mut Settings) {
@@ -88,6 +111,11 @@ fn tabs(ui: &mut Ui, settings: &mut Settings) {
             settings.tab = Tab::Visuals;
         }
 
+        #[cfg(any(feature = "feature-aimbot", feature = "feature-triggerbot"))]
+        if ui.selectable_label(settings.tab == Tab::Aimbot, "aimbot").clicked() {
+            settings.tab = Tab::Aimbot;
+        }
+
         if ui.selectable_label(settings.tab == Tab::Misc, "misc").clicked() {
             settings.tab = Tab::Misc;
         }
@@ -95,18 +123,323 @@ fn tabs(ui: &mut Ui, settings: &mut Settings) {
 }
 
 fn visuals_tab(ui: &mut Ui, settings: &mut VisualsSettings) {
+    #[cfg(feature = "feature-esp")]
+    {
+        esp_section(ui, settings);
+        ui.separator();
+    }
+
+    #[cfg(feature = "feature-radar")]
+    {
+        radar_section(ui, settings);
+        ui.separator();
+    }
+
+    ui.checkbox(&mut settings.draw_self_health, "self health");
+}
+
+#[cfg(feature = "feature-esp")]
+fn esp_section(ui: &mut Ui, settings: &mut VisualsSettings) {
     ui.label("esp");
 
     ui.checkbox(&mut settings.esp.enabled, "enable");
 
-    ui.horizontal(|ui| {
-        ui.checkbox(&mut settings.esp.draw_boxes, "box");
-        ui.color_edit_button_srgba(&mut settings.esp.box_color);
-    });
-
+    ui.checkbox(&mut settings.esp.draw_boxes, "box");
+    ui.checkbox(&mut settings.esp.draw_skeleton, "skeleton");
+    ui.checkbox(&mut settings.esp.show_teammates, "teammates");
+    ui.checkbox(&mut settings.esp.show_dormant, "dormant");
+    ui.checkbox(&mut settings.esp.show_bots, "bots");
     ui.checkbox(&mut settings.esp.draw_nametags, "name");
+    ui.checkbox(&mut settings.esp.draw_weapon, "weapon");
     ui.checkbox(&mut settings.esp.draw_health, "health");
     ui.checkbox(&mut settings.esp.draw_money, "money");
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut settings.esp.draw_snaplines, "snaplines");
+        ui.color_edit_button_srgba(&mut settings.esp.snapline_color);
+    });
+
+    ui.label("esp colors");
+
+    ui.horizontal(|ui| {
+        ui.color_edit_button_srgba(&mut settings.esp.enemy_color);
+        ui.label("enemy");
+        ui.color_edit_button_srgba(&mut settings.esp.team_color);
+        ui.label("team");
+    });
+}
+
+#[cfg(feature = "feature-radar")]
+fn radar_section(ui: &mut Ui, settings: &mut VisualsSettings) {
+    use settings::RadarSettings;
+
+    ui.label("radar");
+
+    ui.checkbox(&mut settings.radar.enabled, "enable");
+    ui.add(
+        Slider::new(&mut settings.radar.zoom, RadarSettings::MIN_ZOOM..=RadarSettings::MAX_ZOOM)
+            .text("zoom"),
+    );
+    ui.add(
+        Slider::new(&mut settings.radar.opacity, RadarSettings::MIN_OPACITY..=1.0).text("opacity"),
+    );
+    ui.checkbox(&mut settings.radar.show_names, "names");
+    ui.checkbox(&mut settings.radar.show_grenades, "grenades");
+
+    ui.add_enabled_ui(settings.radar.show_grenades, |ui| {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut settings.radar.show_he_grenades, "he");
+            ui.checkbox(&mut settings.radar.show_flashbangs, "flash");
+            ui.checkbox(&mut settings.radar.show_smokes, "smoke");
+            ui.checkbox(&mut settings.radar.show_molotovs, "molotov");
+            ui.checkbox(&mut settings.radar.show_decoys, "decoy");
+        });
+    });
+}
+
+#[cfg(any(feature = "feature-aimbot", feature = "feature-triggerbot"))]
+fn aimbot_tab(ui: &mut Ui, settings: &mut Settings) {
+    #[cfg(feature = "feature-aimbot")]
+    {
+        aimbot_section(ui, &mut settings.aimbot);
+        ui.separator();
+    }
+
+    #[cfg(feature = "feature-triggerbot")]
+    triggerbot_section(ui, &mut settings.triggerbot);
+}
+
+#[cfg(feature = "feature-aimbot")]
+fn aimbot_section(ui: &mut Ui, settings: &mut AimbotSettings) {
+    ui.label("aimbot");
+
+    ui.checkbox(&mut settings.enabled, "enable");
+    ui.add(Slider::new(&mut settings.fov, 0.0..=AimbotSettings::MAX_FOV).text("fov"));
+    ui.add(
+        Slider::new(
+            &mut settings.smoothing,
+            AimbotSettings::MIN_SMOOTHING..=AimbotSettings::MAX_SMOOTHING,
+        )
+        .text("smoothing"),
+    );
+
+    ComboBox::from_label("bone").selected_text(settings.bone.name()).show_ui(ui, |ui| {
+        for bone in AimbotBone::ALL {
+            ui.selectable_value(&mut settings.bone, bone, bone.name());
+        }
+    });
+
+    ui.add(
+        DragValue::new(&mut settings.key)
+            .clamp_range(1..=0xFE)
+            .hexadecimal(2, false, true)
+            .prefix("key 0x"),
+    );
+
+    ui.separator();
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut settings.show_fov_circle, "fov circle");
+        ui.color_edit_button_srgba(&mut settings.fov_circle_color);
+    });
+
+    ui.add_enabled_ui(settings.show_fov_circle, |ui| {
+        ui.checkbox(&mut settings.fov_circle_always_on, "always on");
+    });
+}
+
+#[cfg(feature = "feature-triggerbot")]
+fn triggerbot_section(ui: &mut Ui, settings: &mut TriggerbotSettings) {
+    ui.label("triggerbot");
+
+    ui.checkbox(&mut settings.enabled, "enable");
+    ui.add(
+        DragValue::new(&mut settings.key)
+            .clamp_range(1..=0xFE)
+            .hexadecimal(2, false, true)
+            .prefix("key 0x"),
+    );
+    ui.add(
+        Slider::new(&mut settings.delay_ms, 0..=TriggerbotSettings::MAX_DELAY_MS)
+            .text("delay (ms)"),
+    );
+    ui.checkbox(&mut settings.team_check, "team check");
+}
+
+fn misc_tab(ui: &mut Ui, settings: &mut Settings) {
+    ui.label("features");
+
+    for feature in FeatureId::ALL {
+        let mut enabled = settings.features_enabled.contains(&feature);
+
+        if ui.checkbox(&mut enabled, feature.name()).changed() {
+            if enabled {
+                settings.features_enabled.insert(feature);
+            } else {
+                settings.features_enabled.remove(&feature);
+            }
+        }
+    }
+
+    if ui.button("stealth preset").clicked() {
+        settings.apply_stealth_preset();
+    }
+
+    ui.separator();
+
+    ui.add(
+        DragValue::new(&mut settings.misc.menu_key)
+            .clamp_range(1..=0xFE)
+            .hexadecimal(2, false, true)
+            .prefix("menu key 0x"),
+    );
+
+    #[cfg(feature = "feature-bhop")]
+    ui.checkbox(&mut settings.misc.bhop_enabled, "bhop");
+
+    #[cfg(feature = "feature-auto-strafe")]
+    ui.checkbox(&mut settings.misc.auto_strafe, "auto strafe");
+    #[cfg(feature = "feature-jump-throw")]
+    ui.add(
+        DragValue::new(&mut settings.misc.jump_throw_key)
+            .clamp_range(0..=0xFE)
+            .hexadecimal(2, false, true)
+            .prefix("jump throw key 0x"),
+    );
+
+    #[cfg(feature = "feature-no-recoil")]
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut settings.misc.no_recoil, "no recoil");
+        ui.add_enabled(
+            settings.misc.no_recoil,
+            Slider::new(&mut settings.misc.no_recoil_factor, 0.0..=1.0).text("factor"),
+        );
+    });
+
+    ui.checkbox(&mut settings.misc.debug_overlay, "debug overlay");
+
+    let log_level = settings.misc.log_level;
+
+    ComboBox::from_label("log level").selected_text(log_level.name()).show_ui(ui, |ui| {
+        for level in LogLevel::ALL {
+            ui.selectable_value(&mut settings.misc.log_level, level, level.name());
+        }
+    });
+
+    if settings.misc.log_level != log_level {
+        if let Err(e) = bootstrap::set_log_level(settings.misc.log_level) {
+            tracing::warn!("failed to change log level: {e:#}");
+        }
+    }
+
+    ui.separator();
+    profiles_section(ui, settings);
+}
+
+fn profiles_section(ui: &mut Ui, settings: &mut Settings) {
+    ui.label("profiles");
+
+    if ui.button("duplicate current").clicked() {
+        let name = settings::duplicate_profile(settings);
+        tracing::info!("saved current settings as {name}");
+    }
+
+    for profile in settings::PROFILES.lock().iter() {
+        ui.horizontal(|ui| {
+            ui.label(&profile.name);
+
+            if ui.button("load").clicked() {
+                // Stay on the current tab instead of jumping to the one the profile was saved on
+                *settings = Settings { tab: settings.tab, ..profile.settings.clone() };
+            }
+        });
+    }
+}
+
+/// Draws the debug overlay in the top-left corner of the screen.
+///
+/// The overlay is independent of the menu and stays visible while the menu is hidden. It shows the
+/// CPU and GPU time of the previous overlay frame, the adapter the game renders on, and the
+/// session statistics.
+///
+/// # Parameters
+///
+/// * `ctx`: A reference to the `Context` struct used for drawing UI elements.
+/// * `settings`: A reference to the `Settings` struct, used to check if the overlay is enabled.
+pub fn draw_debug_overlay(ctx: &Context, settings: &Settings) {
+    if !settings.misc.debug_overlay {
+        return;
+    }
+
+    let stats = *dx11::FRAME_STATS.lock();
+
+    Area::new("debug_overlay").fixed_pos(Pos2::new(8.0, 8.0)).interactable(false).show(ctx, |ui| {
+        ui.label(RichText::new(format!("cpu: {:.2} ms", stats.cpu_ms)).color(Color32::WHITE));
+
+        let gpu = stats.gpu_ms.map_or_else(|| "n/a".to_owned(), |ms| format!("{ms:.2} ms"));
+        ui.label(RichText::new(format!("gpu: {gpu}")).color(Color32::WHITE));
+
+        if let Some(adapter) = dx11::ADAPTER.get() {
+            let color = if adapter.is_integrated { Color32::YELLOW } else { Color32::WHITE };
+            ui.label(
+                RichText::new(format!("{} ({} MB)", adapter.description, adapter.vram_mb))
+                    .color(color),
+            );
+        }
+
+        let session = *stats::session_stats();
+        ui.label(
+            RichText::new(format!(
+                "frames: {}, esp drawn: {}",
+                session.frames_rendered, session.esp_entities_drawn
+            ))
+            .color(Color32::WHITE),
+        );
+        ui.label(
+            RichText::new(format!(
+                "aimbot: {} locked, {} shots",
+                session.aimbot_targets_locked, session.aimbot_shots_taken
+            ))
+            .color(Color32::WHITE),
+        );
+    });
+}
+
+/// Characters cycled through by the loading indicator.
+const SPINNER_FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+
+/// Number of loading indicator characters shown per second.
+const SPINNER_SPEED: f64 = 8.0;
+
+/// Draws the initialization status at the top of the screen.
+///
+/// While the cheat is initializing, a spinning loading indicator is shown. If initialization
+/// failed, the error is shown instead, so a missed pattern scan does not go unnoticed. Nothing is
+/// drawn once the cheat is ready.
+///
+/// # Parameters
+///
+/// * `ctx`: A reference to the `Context` struct used for drawing UI elements.
+pub fn draw_bootstrap_status(ctx: &Context) {
+    let (text, color) = match bootstrap::status() {
+        BootstrapStatus::Uninitialized | BootstrapStatus::Ready => return,
+        BootstrapStatus::Initializing => {
+            let frame = (ctx.input(|input| input.time) * SPINNER_SPEED) as usize;
+            let spinner = SPINNER_FRAMES[frame % SPINNER_FRAMES.len()];
+
+            (format!("{spinner} initializing..."), Color32::WHITE)
+        }
+        BootstrapStatus::Failed(message) => {
+            (format!("initialization failed: {message}"), Color32::RED)
+        }
+    };
+
+    Area::new("bootstrap_status")
+        .anchor(Align2::CENTER_TOP, Vec2::new(0.0, 8.0))
+        .interactable(false)
+        .show(ctx, |ui| {
+            ui.label(RichText::new(text).color(color));
+        });
 }
 
 /// Determines whether input events should be blocked for a specific window message.
@@ -122,7 +455,7 @@ fn visuals_tab(ui: &mut Ui, settings: &mut VisualsSettings) {
 /// # Return
 ///
 /// * `bool`: A boolean value indicating whether the input event corresponding to the given window message
-///           should be blocked.
+///   should be blocked.
 pub fn should_block_input(msg: u32) -> bool {
     let menu_visible = SHOW_MENU.load(Ordering::SeqCst);
 
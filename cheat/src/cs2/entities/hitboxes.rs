@@ -0,0 +1,57 @@
+//! The hitboxes of the player models, by index into their hitbox set.
+//!
+//! Hitboxes are not bones: several hitboxes can follow the same bone, and the indices differ from
+//! the bone indices in `skeleton::Bone`.
+
+pub const HITBOX_HEAD: u32 = 0;
+pub const HITBOX_NECK: u32 = 1;
+pub const HITBOX_PELVIS: u32 = 2;
+pub const HITBOX_STOMACH: u32 = 3;
+pub const HITBOX_CHEST: u32 = 4;
+pub const HITBOX_LOWER_CHEST: u32 = 5;
+pub const HITBOX_UPPER_CHEST: u32 = 6;
+pub const HITBOX_RIGHT_THIGH: u32 = 7;
+pub const HITBOX_LEFT_THIGH: u32 = 8;
+pub const HITBOX_RIGHT_CALF: u32 = 9;
+pub const HITBOX_LEFT_CALF: u32 = 10;
+pub const HITBOX_RIGHT_FOOT: u32 = 11;
+pub const HITBOX_LEFT_FOOT: u32 = 12;
+pub const HITBOX_RIGHT_HAND: u32 = 13;
+pub const HITBOX_LEFT_HAND: u32 = 14;
+pub const HITBOX_RIGHT_UPPER_ARM: u32 = 15;
+pub const HITBOX_RIGHT_FOREARM: u32 = 16;
+pub const HITBOX_LEFT_UPPER_ARM: u32 = 17;
+pub const HITBOX_LEFT_FOREARM: u32 = 18;
+
+/// The part of the body a hit landed in, which decides the damage multiplier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HitboxGroup {
+    Head,
+    Chest,
+    Stomach,
+    LeftArm,
+    RightArm,
+    LeftLeg,
+    RightLeg,
+    /// Anything that is not part of the body, such as hits on equipment.
+    Gear,
+}
+
+impl HitboxGroup {
+    /// Returns the group of a hitbox, see the `HITBOX_*` constants.
+    ///
+    /// The neck is grouped with the chest. Unknown hitboxes are `Gear`.
+    #[must_use]
+    pub const fn from_hitbox_id(id: u32) -> Self {
+        match id {
+            HITBOX_HEAD => Self::Head,
+            HITBOX_NECK | HITBOX_CHEST | HITBOX_LOWER_CHEST | HITBOX_UPPER_CHEST => Self::Chest,
+            HITBOX_PELVIS | HITBOX_STOMACH => Self::Stomach,
+            HITBOX_LEFT_HAND | HITBOX_LEFT_UPPER_ARM | HITBOX_LEFT_FOREARM => Self::LeftArm,
+            HITBOX_RIGHT_HAND | HITBOX_RIGHT_UPPER_ARM | HITBOX_RIGHT_FOREARM => Self::RightArm,
+            HITBOX_LEFT_THIGH | HITBOX_LEFT_CALF | HITBOX_LEFT_FOOT => Self::LeftLeg,
+            HITBOX_RIGHT_THIGH | HITBOX_RIGHT_CALF | HITBOX_RIGHT_FOOT => Self::RightLeg,
+            _ => Self::Gear,
+        }
+    }
+}
@@ -0,0 +1,423 @@
+pub mod hitboxes;
+mod player_pawn;
+pub mod skeleton;
+pub mod weapon;
+
+pub use player_pawn::CCSPlayerPawn;
+
+use std::ffi::CStr;
+
+use crate::{
+    common::{c_char, c_void},
+    cs2::interfaces,
+    utils::memory,
+};
+
+/// Number of entity identities in each chunk of the entity list.
+const CHUNK_SIZE: usize = 512;
+
+/// Number of chunks in the entity list.
+const MAX_CHUNKS: usize = 64;
+
+/// Highest entity index used by player controllers. Index `0` is the world.
+///
+/// The controllers only go up to the server's player count, see `EntitySystem::players`.
+const MAX_PLAYERS: usize = 64;
+
+/// Designer name of player pawns (`C_CSPlayerPawn`).
+const PLAYER_PAWN_DESIGNER_NAME: &str = "player";
+
+/// Offset of `CCSPlayerController::m_hPlayerPawn`.
+///
+/// Schema offsets move between game updates and have to be refreshed from a schema dump.
+pub(crate) const M_H_PLAYER_PAWN: usize = 0x7E4;
+
+/// Offset of `C_CSPlayerPawn::m_ArmorValue`.
+const M_ARMOR_VALUE: usize = 0x241C;
+
+/// Offset of `CBasePlayerController::m_iConnected`.
+const M_I_CONNECTED: usize = 0x63C;
+
+/// `PlayerConnectedState::PlayerConnected`, the connection state of a player in the game.
+const PLAYER_CONNECTED: u32 = 0;
+
+/// Offset of `CBasePlayerController::m_iszPlayerName`.
+const M_ISZ_PLAYER_NAME: usize = 0x640;
+
+/// Maximum length of `m_iszPlayerName`, including the null terminator.
+const PLAYER_NAME_LENGTH: usize = 128;
+
+/// Offset of `CBasePlayerController::m_steamID`.
+const M_STEAM_ID: usize = 0x6C8;
+
+/// Offset of `C_CSPlayerPawnBase::m_pClippingWeapon`.
+const M_P_CLIPPING_WEAPON: usize = 0x12A8;
+
+/// Offset of `CEntityInstance::m_pEntity`, the identity of an entity.
+const M_P_ENTITY: usize = 0x10;
+
+/// Offset of `C_CSPlayerPawnBase::m_hPlayerInCrosshair`.
+const M_H_PLAYER_IN_CROSSHAIR: usize = 0x1544;
+
+/// Offset of `C_CSPlayerPawnBase::m_angEyeAngles`.
+const M_ANG_EYE_ANGLES: usize = 0x1578;
+
+/// Offset of `C_BaseEntity::m_pGameSceneNode`.
+const M_P_GAME_SCENE_NODE: usize = 0x328;
+
+/// Offset of `C_BaseEntity::m_iHealth`.
+const M_I_HEALTH: usize = 0x344;
+
+/// Offset of `C_BaseEntity::m_iTeamNum`.
+const M_I_TEAM_NUM: usize = 0x3E3;
+
+/// Offset of `C_BaseEntity::m_fFlags`.
+const M_F_FLAGS: usize = 0x3EC;
+
+/// Bit of `m_fFlags` that is set while the entity stands on the ground.
+pub const FL_ONGROUND: u32 = 1 << 0;
+
+/// Offset of `C_BaseEntity::m_vecAbsVelocity`.
+const M_VEC_ABS_VELOCITY: usize = 0x3F0;
+
+/// Offset of `CGameSceneNode::m_vecAbsOrigin`.
+const M_VEC_ABS_ORIGIN: usize = 0xD0;
+
+/// Offset of `CGameSceneNode::m_bDormant`.
+const M_B_DORMANT: usize = 0xEF;
+
+/// A reference to an entity by its slot in the entity list.
+///
+/// The low 15 bits hold the entity index, the remaining bits a serial number that changes every
+/// time the slot is reused. A handle whose serial does not match the slot's current entity refers
+/// to an entity that no longer exists.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EntityHandle(u32);
+
+impl EntityHandle {
+    /// The handle stored in fields that do not refer to any entity.
+    pub const INVALID: Self = Self(0xFFFF_FFFF);
+
+    const INDEX_MASK: u32 = 0x7FFF;
+    const SERIAL_SHIFT: u32 = 15;
+
+    /// Returns the index of the entity list slot the handle refers to.
+    #[must_use]
+    pub const fn index(self) -> usize {
+        (self.0 & Self::INDEX_MASK) as usize
+    }
+
+    /// Returns the serial number of the entity the handle refers to.
+    #[must_use]
+    pub const fn serial(self) -> u32 {
+        self.0 >> Self::SERIAL_SHIFT
+    }
+
+    /// Checks whether the handle still refers to a live entity.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the handle is not `INVALID`, the entity list slot it points to is occupied, and
+    /// the serial number of the entity in that slot matches the handle's serial number.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.identity().is_some()
+    }
+
+    /// Returns the entity the handle refers to, or `None` if the handle is not valid.
+    #[must_use]
+    pub fn get(&self) -> Option<*const c_void> {
+        self.identity().map(|identity| identity.entity)
+    }
+
+    /// Returns the entity list entry the handle refers to, or `None` if the handle is not valid.
+    #[must_use]
+    pub fn identity(&self) -> Option<&'static EntityIdentity> {
+        if *self == Self::INVALID {
+            return None;
+        }
+
+        interfaces::game_resource_service()
+            .entity_system()?
+            .identity(self.index())
+            .filter(|identity| identity.handle.serial() == self.serial())
+    }
+}
+
+/// The entry describing an entity in the entity list (`CEntityIdentity`).
+#[repr(C)]
+pub struct EntityIdentity {
+    /// The entity itself.
+    pub entity: *const c_void,
+    _pad0: [u8; 0x8],
+
+    /// The handle of the entity, holding the serial number of the slot's current occupant.
+    pub handle: EntityHandle,
+    _pad1: [u8; 0xC],
+
+    /// The entity's designer name, such as `cs_player_controller`.
+    designer_name: *const c_char,
+    _pad2: [u8; 0x50],
+}
+
+impl EntityIdentity {
+    /// Returns the entity's designer name, such as `cs_player_controller` or `weapon_ak47`.
+    #[must_use]
+    pub fn designer_name(&self) -> Option<&'static str> {
+        if self.designer_name.is_null() {
+            return None;
+        }
+
+        // SAFETY: Designer names are null-terminated strings owned by the entity's class, which
+        // outlives the entity.
+        unsafe { CStr::from_ptr(self.designer_name) }.to_str().ok()
+    }
+
+    /// Checks whether the entity is a player pawn, by its designer name.
+    #[must_use]
+    pub fn is_player_pawn(&self) -> bool {
+        self.designer_name() == Some(PLAYER_PAWN_DESIGNER_NAME)
+    }
+
+    /// Views the entity as a player pawn, or returns `None` if it is not one.
+    #[must_use]
+    pub fn as_player_pawn(&self) -> Option<&'static CCSPlayerPawn> {
+        if self.entity.is_null() || !self.is_player_pawn() {
+            return None;
+        }
+
+        // SAFETY: The entity of a player pawn's identity is a `C_CSPlayerPawn`, which the entity
+        // system keeps alive at least until the end of the tick. The `'static` lifetime is a lie,
+        // like in `memory::game_slice`, so callers must not keep the reference.
+        Some(unsafe { &*self.entity.cast::<CCSPlayerPawn>() })
+    }
+
+    /// Returns the entity's health.
+    #[must_use]
+    pub fn health(&self) -> Option<i32> {
+        memory::read(self.entity as usize + M_I_HEALTH)
+    }
+
+    /// Returns the entity's armor. Only meaningful for player pawns.
+    #[must_use]
+    pub fn armor(&self) -> Option<i32> {
+        memory::read(self.entity as usize + M_ARMOR_VALUE)
+    }
+
+    /// Returns the yaw the entity is looking at, in degrees. Only meaningful for player pawns.
+    #[must_use]
+    pub fn eye_yaw(&self) -> Option<f32> {
+        memory::read::<[f32; 3]>(self.entity as usize + M_ANG_EYE_ANGLES).map(|[_, yaw, _]| yaw)
+    }
+
+    /// Returns the handle of the entity under the player's crosshair. Only meaningful for player
+    /// pawns.
+    #[must_use]
+    pub fn player_in_crosshair(&self) -> Option<EntityHandle> {
+        memory::read(self.entity as usize + M_H_PLAYER_IN_CROSSHAIR)
+    }
+
+    /// Returns the player's name. Only meaningful for player controllers.
+    #[must_use]
+    pub fn player_name(&self) -> Option<String> {
+        let name =
+            memory::read::<[u8; PLAYER_NAME_LENGTH]>(self.entity as usize + M_ISZ_PLAYER_NAME)?;
+
+        CStr::from_bytes_until_nul(&name).ok().map(|name| name.to_string_lossy().into_owned())
+    }
+
+    /// Checks whether the player is connected and in the game. Only meaningful for player
+    /// controllers.
+    ///
+    /// # Returns
+    ///
+    /// `false` while the player is connecting or after they disconnected, and if the state cannot
+    /// be read.
+    #[must_use]
+    pub fn is_connected(&self) -> bool {
+        memory::read::<u32>(self.entity as usize + M_I_CONNECTED)
+            .is_some_and(|state| state == PLAYER_CONNECTED)
+    }
+
+    /// Checks whether the player is a bot, which has no Steam ID. Only meaningful for player
+    /// controllers.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the Steam ID cannot be read.
+    #[must_use]
+    pub fn is_bot(&self) -> bool {
+        memory::read::<u64>(self.entity as usize + M_STEAM_ID).is_some_and(|steam_id| steam_id == 0)
+    }
+
+    /// Returns the identity of the weapon the player is holding. Only meaningful for player
+    /// pawns.
+    #[must_use]
+    pub fn active_weapon(&self) -> Option<&'static Self> {
+        let weapon = memory::read::<usize>(self.entity as usize + M_P_CLIPPING_WEAPON)?;
+        let identity = memory::read::<usize>(weapon + M_P_ENTITY)?;
+
+        memory::game_slice(identity as *const Self, 1).map(|identity| &identity[0])
+    }
+
+    /// Returns the entity's team number.
+    #[must_use]
+    pub fn team(&self) -> Option<u8> {
+        memory::read(self.entity as usize + M_I_TEAM_NUM)
+    }
+
+    /// Returns the entity's `m_fFlags`, see `FL_ONGROUND`.
+    #[must_use]
+    pub fn flags(&self) -> Option<u32> {
+        memory::read(self.entity as usize + M_F_FLAGS)
+    }
+
+    /// Returns the entity's position in the world.
+    #[must_use]
+    pub fn origin(&self) -> Option<[f32; 3]> {
+        let scene_node = memory::read::<usize>(self.entity as usize + M_P_GAME_SCENE_NODE)?;
+
+        memory::read(scene_node + M_VEC_ABS_ORIGIN)
+    }
+
+    /// Checks whether the game has stopped updating the entity because it left the local
+    /// client's PVS. The position and state of a dormant entity are stale.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the entity is dormant or its scene node cannot be read.
+    #[must_use]
+    pub fn is_dormant(&self) -> bool {
+        memory::read::<usize>(self.entity as usize + M_P_GAME_SCENE_NODE)
+            .and_then(|scene_node| memory::read::<u8>(scene_node + M_B_DORMANT))
+            .is_none_or(|dormant| dormant != 0)
+    }
+
+    /// Returns the entity's velocity, in units per second.
+    #[must_use]
+    pub fn velocity(&self) -> Option<[f32; 3]> {
+        memory::read(self.entity as usize + M_VEC_ABS_VELOCITY)
+    }
+}
+
+/// The game's entity system (`CGameEntitySystem`).
+#[repr(C)]
+pub struct EntitySystem {
+    _pad0: [u8; 0x10],
+
+    /// Chunks of `CHUNK_SIZE` identities. Chunks are allocated on demand, so unused ones are null.
+    chunks: [*const EntityIdentity; MAX_CHUNKS],
+}
+
+impl EntitySystem {
+    /// Returns the identity stored in the entity list slot at `index`.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the index is out of range, its chunk has not been allocated, or the slot is
+    /// empty.
+    #[must_use]
+    pub fn identity(&self, index: usize) -> Option<&'static EntityIdentity> {
+        let chunk = memory::game_slice(*self.chunks.get(index / CHUNK_SIZE)?, CHUNK_SIZE)?;
+        let identity = &chunk[index % CHUNK_SIZE];
+
+        (!identity.entity.is_null()).then_some(identity)
+    }
+
+    /// Returns the entity in the entity list slot at `index`.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the index is negative or out of range, its chunk has not been allocated, or the
+    /// slot is empty.
+    #[must_use]
+    pub fn get_entity(&self, index: i32) -> Option<*const c_void> {
+        self.identity(usize::try_from(index).ok()?).map(|identity| identity.entity)
+    }
+
+    /// Iterates over every player pawn in the entity list, identified by its designer name.
+    ///
+    /// Unlike `player_pawns`, this does not go through the player controllers, so it also yields
+    /// pawns that no controller currently owns.
+    pub fn iter_players(&self) -> impl Iterator<Item = *const c_void> + '_ {
+        self.entities()
+            .filter(|(_, identity)| identity.is_player_pawn())
+            .map(|(_, identity)| identity.entity)
+    }
+
+    /// Iterates over every occupied slot of the entity list, with its index.
+    pub fn entities(&self) -> impl Iterator<Item = (usize, &'static EntityIdentity)> + '_ {
+        self.chunks
+            .iter()
+            .enumerate()
+            .filter_map(|(chunk_index, &chunk)| {
+                Some((chunk_index * CHUNK_SIZE, memory::game_slice(chunk, CHUNK_SIZE)?))
+            })
+            .flat_map(|(first_index, chunk)| {
+                chunk.iter().enumerate().map(move |(slot, identity)| (first_index + slot, identity))
+            })
+            .filter(|(_, identity)| !identity.entity.is_null())
+    }
+
+    /// Returns the pawn of the local player, or `None` if the local player has no live pawn
+    /// (for example, while spectating).
+    ///
+    /// The local player's controller is looked up by the index the engine reports for it, see
+    /// `EngineClient::get_local_player_index`.
+    #[must_use]
+    pub fn local_player_pawn(&self) -> Option<&'static EntityIdentity> {
+        let index = usize::try_from(interfaces::engine_client().get_local_player_index()).ok()?;
+        let controller = self.identity(index)?;
+
+        memory::read::<EntityHandle>(controller.entity as usize + M_H_PLAYER_PAWN)?.identity()
+    }
+
+    /// Collects the pawns of all connected players.
+    ///
+    /// # Returns
+    ///
+    /// The pawn of every player controller with a valid pawn handle.
+    #[must_use]
+    pub fn player_pawns(&self) -> Vec<&'static EntityIdentity> {
+        self.players().into_iter().map(|(_, pawn)| pawn).collect()
+    }
+
+    /// Collects the controllers of all connected players, together with their pawns.
+    ///
+    /// Controllers whose `m_hPlayerPawn` does not refer to a live entity (for example, a player
+    /// that has not spawned yet, or a pawn that was just destroyed) are skipped, since
+    /// dereferencing their pawn would read freed memory. In debug builds the number of skipped
+    /// controllers is logged.
+    ///
+    /// # Returns
+    ///
+    /// Every player controller with a valid pawn handle, paired with its pawn.
+    #[must_use]
+    pub fn players(&self) -> Vec<(&'static EntityIdentity, &'static EntityIdentity)> {
+        let mut players = Vec::new();
+        let mut invalid_handles = 0;
+
+        // Player controllers occupy the slots right after the world, one per player slot
+        let max_clients = usize::try_from(interfaces::engine_client().get_max_clients())
+            .map_or(MAX_PLAYERS, |max_clients| max_clients.min(MAX_PLAYERS));
+
+        for controller in (1..=max_clients).filter_map(|index| self.identity(index)) {
+            let pawn = memory::read::<EntityHandle>(controller.entity as usize + M_H_PLAYER_PAWN)
+                .and_then(|pawn_handle| pawn_handle.identity());
+
+            match pawn {
+                Some(pawn) => players.push((controller, pawn)),
+                None => invalid_handles += 1,
+            }
+        }
+
+        if cfg!(debug_assertions) && invalid_handles > 0 {
+            tracing::debug!(
+                "skipped {invalid_handles} player controllers with invalid pawn handles"
+            );
+        }
+
+        players
+    }
+}
@@ -0,0 +1,157 @@
+use memory_macros::{vfunc, vmt};
+
+use crate::utils::memory;
+
+use super::{
+    skeleton::{Bone, BoneMatrix, BONE_ARRAY, MAX_BONES, M_MODEL_STATE},
+    weapon::WeaponSlot,
+    EntityIdentity, M_P_ENTITY, M_P_GAME_SCENE_NODE,
+};
+
+/// Offset of `C_CSPlayerPawn::m_aimPunchAngle`.
+const M_AIM_PUNCH_ANGLE: usize = 0x1714;
+
+/// Offset of `C_CSPlayerPawn::m_iShotsFired`.
+const M_I_SHOTS_FIRED: usize = 0x23E4;
+
+/// Offset of `C_CSPlayerPawnBase::m_bIsDefusing`.
+const M_B_IS_DEFUSING: usize = 0x13B0;
+
+/// Offset of `C_BasePlayerPawn::m_vOldOrigin`.
+const M_V_OLD_ORIGIN: usize = 0x1224;
+
+/// Offset of `C_BaseModelEntity::m_vecViewOffset`. Its height shrinks while the player crouches.
+const M_VEC_VIEW_OFFSET: usize = 0xC58;
+
+/// Offset of `C_BasePlayerPawn::m_pMovementServices`.
+const M_P_MOVEMENT_SERVICES: usize = 0x1138;
+
+/// Offset of `CCSPlayer_MovementServices::m_flDuckAmount`.
+const M_FL_DUCK_AMOUNT: usize = 0x1EC;
+
+/// Height of a standing player's eyes above their origin, in world units.
+const STANDING_VIEW_HEIGHT: f32 = 64.06;
+
+/// Height of a fully crouched player's eyes above their origin, in world units.
+const CROUCHED_VIEW_HEIGHT: f32 = 46.05;
+
+/// A player's pawn (`C_CSPlayerPawn`), the entity that represents the player in the world.
+///
+/// Virtual function indices move between game updates, like schema offsets, and have to be
+/// checked against the current `client.dll`.
+#[vmt]
+pub struct CCSPlayerPawn {}
+
+impl CCSPlayerPawn {
+    /// Returns the pawn's health.
+    #[vfunc(166)]
+    pub fn health(&self) -> i32 {}
+
+    /// Returns the pawn's team number.
+    #[vfunc(167)]
+    pub fn team_num(&self) -> i32 {}
+
+    /// Returns the position of the pawn's eyes in the world, `m_vOldOrigin + m_vecViewOffset`.
+    ///
+    /// The height of the view offset is interpolated between the standing and crouched eye
+    /// heights by `get_crouch_state`, so it is also right halfway through the crouch animation.
+    /// The networked height is used if the crouch state cannot be read.
+    #[must_use]
+    pub fn get_eye_position(&self) -> Option<[f32; 3]> {
+        let address = self as *const Self as usize;
+        let origin = memory::read::<[f32; 3]>(address + M_V_OLD_ORIGIN)?;
+        let [x, y, mut z] = memory::read::<[f32; 3]>(address + M_VEC_VIEW_OFFSET)?;
+
+        if let Some(duck_amount) = self.get_crouch_state() {
+            z = STANDING_VIEW_HEIGHT + (CROUCHED_VIEW_HEIGHT - STANDING_VIEW_HEIGHT) * duck_amount;
+        }
+
+        Some([origin[0] + x, origin[1] + y, origin[2] + z])
+    }
+
+    /// Returns how far the pawn has crouched, `m_flDuckAmount`.
+    ///
+    /// # Returns
+    ///
+    /// From 0 while standing to 1 while fully crouched, or `None` if the pawn has no movement
+    /// services.
+    #[must_use]
+    pub fn get_crouch_state(&self) -> Option<f32> {
+        let movement_services =
+            memory::read::<usize>(self as *const Self as usize + M_P_MOVEMENT_SERVICES)?;
+
+        memory::read::<f32>(movement_services + M_FL_DUCK_AMOUNT)
+            .map(|duck_amount| duck_amount.clamp(0.0, 1.0))
+    }
+
+    /// Returns the slot of the weapon the pawn is holding.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the pawn holds no weapon or its identity cannot be read.
+    #[must_use]
+    pub fn get_active_weapon_slot(&self) -> Option<WeaponSlot> {
+        let identity = memory::read::<usize>(self as *const Self as usize + M_P_ENTITY)?;
+        let identity = memory::game_slice(identity as *const EntityIdentity, 1)?;
+
+        WeaponSlot::from_designer_name(identity[0].active_weapon()?.designer_name()?)
+    }
+
+    /// Checks whether the pawn is defusing the bomb.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the flag cannot be read.
+    #[must_use]
+    pub fn is_defusing(&self) -> bool {
+        memory::read::<u8>(self as *const Self as usize + M_B_IS_DEFUSING)
+            .is_some_and(|defusing| defusing != 0)
+    }
+
+    /// Returns the recoil the pawn's weapon currently applies to its aim (pitch, yaw, roll), in
+    /// degrees. The view moves by twice this angle.
+    #[must_use]
+    pub fn aim_punch_angle(&self) -> Option<[f32; 3]> {
+        memory::read(self as *const Self as usize + M_AIM_PUNCH_ANGLE)
+    }
+
+    /// Returns the number of shots the pawn fired since it last released the attack button,
+    /// `m_iShotsFired`.
+    #[must_use]
+    pub fn shots_fired(&self) -> Option<i32> {
+        memory::read(self as *const Self as usize + M_I_SHOTS_FIRED)
+    }
+
+    /// Returns the world transform of a bone of the pawn's model, see `skeleton::Bone` for the
+    /// indices of the skeleton bones.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the index is out of range, or the pawn has no scene node or bones (for example,
+    /// while it is dormant).
+    #[must_use]
+    pub fn get_bone_matrix(&self, bone_index: u32) -> Option<BoneMatrix> {
+        if bone_index >= MAX_BONES {
+            return None;
+        }
+
+        let scene_node = memory::read::<usize>(self as *const Self as usize + M_P_GAME_SCENE_NODE)?;
+        let bones = memory::read::<usize>(scene_node + M_MODEL_STATE + BONE_ARRAY)?;
+
+        memory::read(bones + bone_index as usize * size_of::<BoneMatrix>())
+    }
+
+    /// Returns the world position of every bone in `Bone::ALL`, in the same order.
+    ///
+    /// # Returns
+    ///
+    /// An empty list if the bones cannot be read.
+    #[must_use]
+    pub fn bone_positions(&self) -> Vec<[f32; 3]> {
+        Bone::ALL
+            .iter()
+            .map(|bone| self.get_bone_matrix(bone.index()).map(|matrix| matrix.translation))
+            .collect::<Option<_>>()
+            .unwrap_or_default()
+    }
+}
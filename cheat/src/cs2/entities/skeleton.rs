@@ -0,0 +1,101 @@
+/// Offset of `CSkeletonInstance::m_modelState` in the pawn's game scene node.
+pub(super) const M_MODEL_STATE: usize = 0x170;
+
+/// Offset of the pointer to the bone transforms in `CModelState`, just before `m_hModel`. It is
+/// not part of the schema and has to be checked by hand after game updates.
+pub(super) const BONE_ARRAY: usize = 0x80;
+
+/// Highest number of bones read from a model. The player models have fewer bones than this.
+pub(super) const MAX_BONES: u32 = 128;
+
+/// The transform of a bone in world space, as stored in the bone array of a model state.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BoneMatrix {
+    /// The bone's position in the world.
+    pub translation: [f32; 3],
+
+    /// The bone's scale. Only kept so the layout matches the game's.
+    pub scale: f32,
+
+    /// The bone's orientation, as a quaternion in `[x, y, z, w]` order.
+    pub rotation: [f32; 4],
+}
+
+/// The bones of the player models that make up their skeleton, by index into the bone array.
+///
+/// The head is bone 6, the neck 5, the chest (`spine_1`) 4 and the pelvis 0. The indices are the
+/// same for every player model.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bone {
+    Pelvis = 0,
+    Spine2 = 2,
+    Chest = 4,
+    Neck = 5,
+    Head = 6,
+    LeftUpperArm = 8,
+    LeftLowerArm = 9,
+    LeftHand = 10,
+    RightUpperArm = 13,
+    RightLowerArm = 14,
+    RightHand = 15,
+    LeftUpperLeg = 22,
+    LeftLowerLeg = 23,
+    LeftAnkle = 24,
+    RightUpperLeg = 25,
+    RightLowerLeg = 26,
+    RightAnkle = 27,
+}
+
+impl Bone {
+    /// Every bone of the skeleton, from the pelvis up and then outwards along the limbs.
+    pub const ALL: [Self; 17] = [
+        Self::Pelvis,
+        Self::Spine2,
+        Self::Chest,
+        Self::Neck,
+        Self::Head,
+        Self::LeftUpperArm,
+        Self::LeftLowerArm,
+        Self::LeftHand,
+        Self::RightUpperArm,
+        Self::RightLowerArm,
+        Self::RightHand,
+        Self::LeftUpperLeg,
+        Self::LeftLowerLeg,
+        Self::LeftAnkle,
+        Self::RightUpperLeg,
+        Self::RightLowerLeg,
+        Self::RightAnkle,
+    ];
+
+    /// Returns the bone's index into the bone array.
+    #[must_use]
+    pub const fn index(self) -> u32 {
+        self as u32
+    }
+}
+
+/// The bones of the skeleton that are connected, as `(parent, child)` pairs of bone indices.
+///
+/// Bones that sit between these in the model (such as `spine_0` and the clavicles) are skipped,
+/// so each pair is one straight line of a stick figure.
+pub const BONE_CONNECTIONS: &[(u32, u32)] = &[
+    (Bone::Pelvis.index(), Bone::Spine2.index()),
+    (Bone::Spine2.index(), Bone::Chest.index()),
+    (Bone::Chest.index(), Bone::Neck.index()),
+    (Bone::Neck.index(), Bone::Head.index()),
+    (Bone::Neck.index(), Bone::LeftUpperArm.index()),
+    (Bone::LeftUpperArm.index(), Bone::LeftLowerArm.index()),
+    (Bone::LeftLowerArm.index(), Bone::LeftHand.index()),
+    (Bone::Neck.index(), Bone::RightUpperArm.index()),
+    (Bone::RightUpperArm.index(), Bone::RightLowerArm.index()),
+    (Bone::RightLowerArm.index(), Bone::RightHand.index()),
+    (Bone::Pelvis.index(), Bone::LeftUpperLeg.index()),
+    (Bone::LeftUpperLeg.index(), Bone::LeftLowerLeg.index()),
+    (Bone::LeftLowerLeg.index(), Bone::LeftAnkle.index()),
+    (Bone::Pelvis.index(), Bone::RightUpperLeg.index()),
+    (Bone::RightUpperLeg.index(), Bone::RightLowerLeg.index()),
+    (Bone::RightLowerLeg.index(), Bone::RightAnkle.index()),
+];
@@ -0,0 +1,126 @@
+/// Prefix of the designer names of weapons, such as `weapon_ak47`.
+const WEAPON_PREFIX: &str = "weapon_";
+
+/// The inventory slot a weapon is carried in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeaponSlot {
+    /// Rifles, sniper rifles, SMGs, shotguns and machine guns.
+    Primary,
+    /// Pistols.
+    Secondary,
+    /// Knives, and the Zeus, which shares their slot.
+    Knife,
+    Grenade,
+    C4,
+}
+
+impl WeaponSlot {
+    /// Classifies a weapon by its designer name, such as `weapon_ak47`.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the name is not a weapon's.
+    #[must_use]
+    pub fn from_designer_name(designer_name: &str) -> Option<Self> {
+        let name = designer_name.strip_prefix(WEAPON_PREFIX)?;
+
+        let slot = match name {
+            "c4" => Self::C4,
+            "hegrenade" | "flashbang" | "smokegrenade" | "molotov" | "incgrenade" | "decoy" => {
+                Self::Grenade
+            }
+            "glock" | "hkp2000" | "usp_silencer" | "p250" | "fiveseven" | "tec9" | "cz75a"
+            | "deagle" | "revolver" | "elite" => Self::Secondary,
+            "taser" | "bayonet" => Self::Knife,
+            name if name.starts_with("knife") => Self::Knife,
+            _ => Self::Primary,
+        };
+
+        Some(slot)
+    }
+}
+
+/// Returns the name the game shows for a weapon, such as `AK-47` for `weapon_ak47`.
+///
+/// # Returns
+///
+/// `None` if the name is not a weapon's. Weapons missing from the table, such as new additions,
+/// are shown by their designer name without the prefix.
+#[must_use]
+pub fn display_name(designer_name: &str) -> Option<&str> {
+    let name = designer_name.strip_prefix(WEAPON_PREFIX)?;
+
+    let display_name = match name {
+        "ak47" => "AK-47",
+        "m4a1" => "M4A4",
+        "m4a1_silencer" => "M4A1-S",
+        "aug" => "AUG",
+        "sg556" => "SG 553",
+        "famas" => "FAMAS",
+        "galilar" => "Galil AR",
+        "awp" => "AWP",
+        "ssg08" => "SSG 08",
+        "scar20" => "SCAR-20",
+        "g3sg1" => "G3SG1",
+        "mac10" => "MAC-10",
+        "mp5sd" => "MP5-SD",
+        "mp7" => "MP7",
+        "mp9" => "MP9",
+        "p90" => "P90",
+        "bizon" => "PP-Bizon",
+        "ump45" => "UMP-45",
+        "nova" => "Nova",
+        "xm1014" => "XM1014",
+        "mag7" => "MAG-7",
+        "sawedoff" => "Sawed-Off",
+        "m249" => "M249",
+        "negev" => "Negev",
+        "glock" => "Glock-18",
+        "hkp2000" => "P2000",
+        "usp_silencer" => "USP-S",
+        "p250" => "P250",
+        "fiveseven" => "Five-SeveN",
+        "tec9" => "Tec-9",
+        "cz75a" => "CZ75-Auto",
+        "deagle" => "Desert Eagle",
+        "revolver" => "R8 Revolver",
+        "elite" => "Dual Berettas",
+        "hegrenade" => "HE Grenade",
+        "flashbang" => "Flashbang",
+        "smokegrenade" => "Smoke Grenade",
+        "molotov" => "Molotov",
+        "incgrenade" => "Incendiary Grenade",
+        "decoy" => "Decoy Grenade",
+        "c4" => "C4",
+        "taser" => "Zeus x27",
+        name if name.starts_with("knife") || name == "bayonet" => "Knife",
+        name => name,
+    };
+
+    Some(display_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{display_name, WeaponSlot};
+
+    #[test]
+    fn weapons_are_classified_by_slot() {
+        assert_eq!(WeaponSlot::from_designer_name("weapon_ak47"), Some(WeaponSlot::Primary));
+        assert_eq!(WeaponSlot::from_designer_name("weapon_deagle"), Some(WeaponSlot::Secondary));
+        assert_eq!(WeaponSlot::from_designer_name("weapon_knife_t"), Some(WeaponSlot::Knife));
+        assert_eq!(WeaponSlot::from_designer_name("weapon_bayonet"), Some(WeaponSlot::Knife));
+        assert_eq!(WeaponSlot::from_designer_name("weapon_flashbang"), Some(WeaponSlot::Grenade));
+        assert_eq!(WeaponSlot::from_designer_name("weapon_c4"), Some(WeaponSlot::C4));
+        assert_eq!(WeaponSlot::from_designer_name("player"), None);
+    }
+
+    #[test]
+    fn display_names_match_the_game() {
+        assert_eq!(display_name("weapon_ak47"), Some("AK-47"));
+        assert_eq!(display_name("weapon_m4a1_silencer"), Some("M4A1-S"));
+        assert_eq!(display_name("weapon_knife_karambit"), Some("Knife"));
+        assert_eq!(display_name("weapon_future_gun"), Some("future_gun"));
+        assert_eq!(display_name("cs_player_controller"), None);
+    }
+}
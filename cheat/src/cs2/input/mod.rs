@@ -0,0 +1,87 @@
+use crate::{common::c_void, utils::memory};
+
+/// Offset of the view angles (pitch, yaw, roll) in `CCSGOInput`, which the game builds the next
+/// command from.
+///
+/// Like schema offsets, the offsets in this file move between game updates and have to be
+/// checked by hand against the current `client.dll`.
+const M_ANG_VIEW_ANGLES: usize = 0x4510;
+
+/// Offset of the bitmask of buttons held for the next command in `CCSGOInput`.
+const M_N_BUTTONS: usize = 0x250;
+
+/// Offset of the sideways movement of the next command in `CCSGOInput`.
+const M_FL_LEFT_MOVE: usize = 0x258;
+
+/// Button bit of the primary attack (`+attack`).
+pub const IN_ATTACK: u64 = 1 << 0;
+
+/// Button bit of jumping (`+jump`).
+pub const IN_JUMP: u64 = 1 << 1;
+
+/// The game's input system (`CCSGOInput`), which turns player input into commands.
+///
+/// Only valid within the `CreateMove` call it was passed to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CCSGOInput {
+    address: usize,
+}
+
+impl CCSGOInput {
+    /// Largest forward or sideways movement of a command. Unlike in CS:GO, where it was the
+    /// speed of 450 units per second, CS2 stores movement as a fraction of full speed.
+    pub const MAX_MOVE: f32 = 1.0;
+
+    /// Wraps the `CCSGOInput` passed to `CreateMove`, or returns `None` if it is null.
+    #[must_use]
+    pub fn from_ptr(input: *mut c_void) -> Option<Self> {
+        (!input.is_null()).then_some(Self { address: input as usize })
+    }
+
+    /// Returns the view angles (pitch, yaw, roll) the next command is built from, in degrees.
+    #[must_use]
+    pub fn view_angles(&self) -> Option<[f32; 3]> {
+        memory::read(self.address + M_ANG_VIEW_ANGLES)
+    }
+
+    /// Overwrites the view angles the next command is built from.
+    ///
+    /// Nothing is written if the view angles cannot be read.
+    pub fn set_view_angles(&self, view_angles: [f32; 3]) {
+        self.write(M_ANG_VIEW_ANGLES, view_angles);
+    }
+
+    /// Returns the bitmask of buttons held for the next command, see `IN_ATTACK`.
+    #[must_use]
+    pub fn buttons(&self) -> Option<u64> {
+        memory::read(self.address + M_N_BUTTONS)
+    }
+
+    /// Overwrites the bitmask of buttons held for the next command.
+    ///
+    /// Nothing is written if the buttons cannot be read.
+    pub fn set_buttons(&self, buttons: u64) {
+        self.write(M_N_BUTTONS, buttons);
+    }
+
+    /// Overwrites the sideways movement of the next command, from `-MAX_MOVE` (right) to
+    /// `MAX_MOVE` (left).
+    ///
+    /// Nothing is written if the movement cannot be read.
+    pub fn set_left_move(&self, left_move: f32) {
+        self.write(M_FL_LEFT_MOVE, left_move.clamp(-Self::MAX_MOVE, Self::MAX_MOVE));
+    }
+
+    /// Writes `value` to the field at `offset`, if the field can be read.
+    fn write<T: Copy + 'static>(&self, offset: usize, value: T) {
+        let address = self.address + offset;
+
+        if memory::read::<T>(address).is_none() {
+            return;
+        }
+
+        // SAFETY: The field was just read, so it lies within the game's `CCSGOInput`, which the
+        // game itself writes to on every command.
+        unsafe { (address as *mut T).write_unaligned(value) };
+    }
+}
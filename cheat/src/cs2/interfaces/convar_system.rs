@@ -0,0 +1,99 @@
+use std::ffi::CStr;
+
+use crate::{common::c_char, utils::memory};
+
+/// Offset of the pointer to the first node of the registered convar list in `CCvar`.
+///
+/// Like schema offsets, the offsets in this file move between game updates and have to be
+/// checked by hand.
+const CONVAR_LIST_OFFSET: usize = 0x40;
+
+/// Offset of the name in a convar.
+const CONVAR_NAME: usize = 0x0;
+
+/// Offset of the help text in a convar.
+const CONVAR_DESCRIPTION: usize = 0x20;
+
+/// Offset of the value type (`EConVarType`) in a convar.
+const CONVAR_TYPE: usize = 0x28;
+
+/// Offset of the `FCVAR_*` flags in a convar.
+const CONVAR_FLAGS: usize = 0x30;
+
+/// Upper bound on the number of nodes visited, so a corrupted list cannot loop forever.
+const MAX_CONVARS: usize = 1 << 16;
+
+/// A node of the registered convar list.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct ConVarNode {
+    convar: usize,
+    next: usize,
+}
+
+pub struct ConVarSystem {}
+
+impl ConVarSystem {
+    /// Iterates over every registered convar, in registration order.
+    ///
+    /// The list is read lazily, one node per item, and ends at the first null node or the first
+    /// node that cannot be read.
+    pub fn iter(&self) -> impl Iterator<Item = ConVar> {
+        let head = memory::read::<usize>(self as *const Self as usize + CONVAR_LIST_OFFSET);
+
+        std::iter::successors(head.and_then(read_node), |node| read_node(node.next))
+            .take(MAX_CONVARS)
+            .filter(|node| node.convar != 0)
+            .map(|node| ConVar { address: node.convar })
+    }
+}
+
+/// Reads the list node at `address`, or returns `None` for the null sentinel.
+fn read_node(address: usize) -> Option<ConVarNode> {
+    (address != 0).then(|| memory::read(address)).flatten()
+}
+
+/// A registered console variable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConVar {
+    address: usize,
+}
+
+impl ConVar {
+    /// Returns the convar's name, such as `sv_cheats`.
+    #[must_use]
+    pub fn name(&self) -> Option<&'static str> {
+        read_str(self.address + CONVAR_NAME)
+    }
+
+    /// Returns the convar's help text.
+    #[must_use]
+    pub fn description(&self) -> Option<&'static str> {
+        read_str(self.address + CONVAR_DESCRIPTION)
+    }
+
+    /// Returns the raw `EConVarType` of the convar's value, such as bool, int32 or string.
+    #[must_use]
+    pub fn value_type(&self) -> Option<i16> {
+        memory::read(self.address + CONVAR_TYPE)
+    }
+
+    /// Returns the convar's `FCVAR_*` flags.
+    #[must_use]
+    pub fn flags(&self) -> Option<u64> {
+        memory::read(self.address + CONVAR_FLAGS)
+    }
+}
+
+/// Reads the null-terminated string that the pointer at `field` points to.
+fn read_str(field: usize) -> Option<&'static str> {
+    let string = memory::read::<usize>(field)?;
+
+    if string == 0 {
+        return None;
+    }
+
+    // SAFETY: Convar names and descriptions are null-terminated strings that live as long as the
+    // convar, which is registered for the rest of the process.
+    unsafe { CStr::from_ptr(string as *const c_char) }.to_str().ok()
+}
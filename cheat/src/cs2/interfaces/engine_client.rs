@@ -3,6 +3,13 @@ use memory_macros::vfunc;
 pub struct EngineClient {}
 
 impl EngineClient {
+    /// Returns the number of player slots on the server.
+    #[vfunc(34)]
+    pub fn get_max_clients(&self) -> i32 {}
     #[vfunc(35)]
     pub fn is_in_game(&self) -> bool {}
+
+    /// Returns the entity list index of the local player's controller.
+    #[vfunc(36)]
+    pub fn get_local_player_index(&self) -> i32 {}
 }
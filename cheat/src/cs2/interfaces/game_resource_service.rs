@@ -0,0 +1,20 @@
+use crate::cs2::entities::EntitySystem;
+
+/// Offset of the `CGameEntitySystem` pointer inside `GameResourceServiceClientV001`.
+const ENTITY_SYSTEM_OFFSET: usize = 0x58;
+
+pub struct GameResourceService {}
+
+impl GameResourceService {
+    /// Returns the game's entity system, or `None` while it has not been created yet (for
+    /// example, in the main menu before the first map is loaded).
+    #[must_use]
+    pub fn entity_system(&self) -> Option<&'static EntitySystem> {
+        let field =
+            (self as *const Self as usize + ENTITY_SYSTEM_OFFSET) as *const *const EntitySystem;
+
+        // SAFETY: `self` points to the game's resource service, which holds the entity system
+        // pointer at `ENTITY_SYSTEM_OFFSET`. The entity system lives for the rest of the process.
+        unsafe { (*field).as_ref() }
+    }
+}
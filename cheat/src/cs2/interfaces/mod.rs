@@ -1,5 +1,8 @@
+pub mod convar_system;
 pub mod engine_client;
+pub mod game_resource_service;
 
+#[cfg_attr(test, allow(unused_imports))]
 use std::sync::atomic::{AtomicPtr, Ordering};
 
 /// This macro is used to define a static reference to a specific interface provided by the game engine.
@@ -15,28 +18,101 @@ use std::sync::atomic::{AtomicPtr, Ordering};
 ///
 /// This macro does not return a value. Instead, it defines a static reference and a function as per the provided parameters.
 ///
-/// The static reference is named `INTERFACE_$name:upper` and is of type `once_cell::sync::Lazy<super::interfaces::$name::Interface>`.
-/// It is initialized using the `once_cell::sync::Lazy::new` function, which creates a new lazy-initialized value.
+/// The static reference is named `INTERFACE_$name:upper` and is of type `std::sync::LazyLock<AtomicPtr<$type>>`.
+/// It is initialized using the `std::sync::LazyLock::new` function, which creates a new lazy-initialized value.
 /// Inside the closure, the interface pointer is obtained by calling the `$module_fn` function, retrieving the interface using the `$interface_name`,
 /// and then creating a new instance of `super::interfaces::$name::Interface` using the obtained interface pointer.
 ///
 /// The function named `$name` is also defined, which returns a reference to the static reference `INTERFACE_$name:upper`.
+///
+/// Interfaces that do not exist in every game mode can be marked as `optional` by appending it to the
+/// parameters, for example `define_interface!(demo_recorder, engine2, "DemoRecorder001", DemoRecorder, optional)`.
+/// The static reference then holds an `Option`, and the generated function returns
+/// `Option<&'static $type>`, which is `None` if the module is not loaded or does not export the interface.
+/// Interfaces that are not optional still panic if they are missing.
+///
+/// In test builds the interface is never looked up in the game. Instead, each thread starts without an
+/// interface and a test installs its own instance with the generated `mock_$name` function (see `cs2::mock`).
 #[macro_export]
 macro_rules! define_interface {
+    ($name:ident, $module_fn:ident, $interface_name:expr, $type:ty, optional) => {
+        paste::paste! {
+            #[cfg(not(test))]
+            static [<INTERFACE_ $name:upper>]: std::sync::LazyLock<Option<AtomicPtr<$type>>> = std::sync::LazyLock::new(|| {
+                $crate::cs2::modules::[<try_ $module_fn>]()?
+                    .get_interface($interface_name)
+                    .filter(|interface_ptr| !interface_ptr.is_null())
+                    .map(|interface_ptr| AtomicPtr::new(interface_ptr as *mut $type))
+            });
+
+            #[cfg(test)]
+            thread_local! {
+                static [<INTERFACE_ $name:upper>]: std::cell::Cell<*const $type> =
+                    const { std::cell::Cell::new(std::ptr::null()) };
+            }
+
+            #[cfg(not(test))]
+            pub fn $name() -> Option<&'static $type> {
+                [<INTERFACE_ $name:upper>]
+                    .as_ref()
+                    .and_then(|interface_ptr| unsafe { interface_ptr.load(Ordering::SeqCst).as_ref() })
+            }
+
+            #[cfg(test)]
+            pub fn $name() -> Option<&'static $type> {
+                unsafe { [<INTERFACE_ $name:upper>].get().as_ref() }
+            }
+
+            /// Replaces the interface with a test-local instance for the current thread.
+            #[cfg(test)]
+            pub fn [<mock_ $name>](interface: &'static $type) {
+                [<INTERFACE_ $name:upper>].set(interface);
+            }
+        }
+    };
+
     ($name:ident, $module_fn:ident, $interface_name:expr, $type:ty) => {
         paste::paste! {
-            static [<INTERFACE_ $name:upper>]: once_cell::sync::Lazy<AtomicPtr<$type>> = once_cell::sync::Lazy::new(|| {
+            #[cfg(not(test))]
+            static [<INTERFACE_ $name:upper>]: std::sync::LazyLock<AtomicPtr<$type>> = std::sync::LazyLock::new(|| {
                 let interface_ptr = $crate::cs2::modules::$module_fn()
                     .get_interface($interface_name)
                     .expect(concat!("failed to find ", $interface_name)) as *mut $type;
                 AtomicPtr::new(interface_ptr)
             });
 
+            #[cfg(test)]
+            thread_local! {
+                static [<INTERFACE_ $name:upper>]: std::cell::Cell<*const $type> =
+                    const { std::cell::Cell::new(std::ptr::null()) };
+            }
+
+            #[cfg(not(test))]
             pub fn $name() -> &'static $type {
                 unsafe { &*([<INTERFACE_ $name:upper>].load(Ordering::SeqCst)) }
             }
+
+            #[cfg(test)]
+            pub fn $name() -> &'static $type {
+                let interface_ptr = [<INTERFACE_ $name:upper>].get();
+                assert!(!interface_ptr.is_null(), concat!(stringify!($name), " is not mocked"));
+                unsafe { &*interface_ptr }
+            }
+
+            /// Replaces the interface with a test-local instance for the current thread.
+            #[cfg(test)]
+            pub fn [<mock_ $name>](interface: &'static $type) {
+                [<INTERFACE_ $name:upper>].set(interface);
+            }
         }
     };
 }
 
 define_interface!(engine_client, engine2, "Source2EngineToClient001", engine_client::EngineClient);
+define_interface!(convar_system, tier0, "VEngineCvar007", convar_system::ConVarSystem, optional);
+define_interface!(
+    game_resource_service,
+    engine2,
+    "GameResourceServiceClientV001",
+    game_resource_service::GameResourceService
+);
@@ -0,0 +1,57 @@
+use anyhow::{bail, Context};
+
+use crate::{
+    common::OnceLock,
+    cs2::{
+        self,
+        entities::{CCSPlayerPawn, EntityHandle, M_H_PLAYER_PAWN},
+        offsets,
+    },
+    utils::memory,
+};
+
+/// Position of the displacement in `mov rax, [rip+offset]`.
+const DISPLACEMENT_OFFSET: usize = 3;
+
+/// Length of `mov rax, [rip+offset]`.
+const INSTRUCTION_LENGTH: usize = 7;
+
+/// The address of the `LocalGamePlayerController` global in `client.dll`, which holds a pointer
+/// to the local player's controller.
+static LOCALPLAYER_BASE: OnceLock<usize> = OnceLock::new();
+
+/// Resolves the address of the `LocalGamePlayerController` global from the
+/// `local_player_controller` offset.
+///
+/// # Errors
+///
+/// Returns an error if the offset was not found, its instruction does not refer to an address in
+/// `client.dll`, or the local player was already initialized.
+pub fn initialize() -> anyhow::Result<()> {
+    let instruction = offsets::get("local_player_controller")? as *const usize;
+    let base = cs2::modules::client()
+        .follow_relative(instruction, DISPLACEMENT_OFFSET, INSTRUCTION_LENGTH)
+        .context("failed to resolve the local player controller global")?;
+
+    if LOCALPLAYER_BASE.set(base as usize).is_err() {
+        bail!("local player is already initialized");
+    }
+
+    tracing::debug!("found local player controller global at {base:p}");
+
+    Ok(())
+}
+
+/// Returns the local player's pawn.
+///
+/// # Returns
+///
+/// `None` if `initialize` has not succeeded, there is no local player controller (for example, in
+/// the main menu), or the controller has no live pawn (for example, while spectating).
+#[must_use]
+pub fn get() -> Option<*const CCSPlayerPawn> {
+    let controller = memory::read::<usize>(*LOCALPLAYER_BASE.get()?)?;
+    let pawn_handle = memory::read::<EntityHandle>(controller + M_H_PLAYER_PAWN)?;
+
+    pawn_handle.get().map(|pawn| pawn.cast::<CCSPlayerPawn>())
+}
@@ -0,0 +1,157 @@
+/// Smallest clip-space `w` a position may have to be projected. Positions with a smaller `w` are
+/// behind the camera or too close to its plane to give a stable screen position.
+const MIN_CLIP_W: f32 = 0.01;
+
+/// Projects a position in the world onto the screen.
+///
+/// # Parameters
+///
+/// * `view_matrix`: The game's row-major world-to-clip matrix, see `cs2::view_matrix`.
+/// * `world_pos`: The position to project.
+/// * `screen_width`: The width of the screen, in pixels.
+/// * `screen_height`: The height of the screen, in pixels.
+///
+/// # Returns
+///
+/// The screen position in pixels, measured from the top left corner, or `None` if the position is
+/// behind the camera. Positions outside of the view are still returned and lie off screen.
+#[must_use]
+pub fn world_to_screen(
+    view_matrix: &[f32; 16],
+    world_pos: [f32; 3],
+    screen_width: f32,
+    screen_height: f32,
+) -> Option<[f32; 2]> {
+    let [x, y, z] = world_pos;
+    let row = |row: usize| {
+        let m = &view_matrix[row * 4..row * 4 + 4];
+        m[0] * x + m[1] * y + m[2] * z + m[3]
+    };
+
+    let w = row(3);
+
+    if w < MIN_CLIP_W {
+        return None;
+    }
+
+    let ndc_x = row(0) / w;
+    let ndc_y = row(1) / w;
+
+    Some([screen_width / 2.0 * (1.0 + ndc_x), screen_height / 2.0 * (1.0 - ndc_y)])
+}
+
+/// Returns the view angles that look from one position at another.
+///
+/// # Parameters
+///
+/// * `from`: The position looked from, such as the local player's eyes.
+/// * `to`: The position looked at.
+///
+/// # Returns
+///
+/// The pitch and yaw in degrees, in the game's convention: positive pitch looks down and yaw is
+/// measured counterclockwise from +x, in `(-180, 180]`.
+#[must_use]
+pub fn calculate_angle(from: [f32; 3], to: [f32; 3]) -> [f32; 2] {
+    let [dx, dy, dz] = [to[0] - from[0], to[1] - from[1], to[2] - from[2]];
+
+    let pitch = (-dz).atan2(dx.hypot(dy)).to_degrees();
+    let yaw = dy.atan2(dx).to_degrees();
+
+    [pitch, yaw]
+}
+
+/// Returns the difference between two view angles, taking the shorter way around for the yaw.
+///
+/// # Returns
+///
+/// `to - from`, with the yaw in `[-180, 180)`.
+#[must_use]
+pub fn angle_delta(from: [f32; 2], to: [f32; 2]) -> [f32; 2] {
+    [to[0] - from[0], normalize_yaw(to[1] - from[1])]
+}
+
+/// Wraps a yaw in degrees into `[-180, 180)`.
+#[must_use]
+pub fn normalize_yaw(yaw: f32) -> f32 {
+    (yaw + 180.0).rem_euclid(360.0) - 180.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{angle_delta, calculate_angle, world_to_screen};
+
+    const WIDTH: f32 = 1920.0;
+    const HEIGHT: f32 = 1080.0;
+
+    /// A camera at the origin looking along +x with a 90 degree field of view: +y is left and +z
+    /// is up, like in the game.
+    const LOOKING_ALONG_X: [f32; 16] = [
+        0.0, -1.0, 0.0, 0.0, //
+        0.0, 0.0, 1.0, 0.0, //
+        0.0, 0.0, 0.0, 0.0, //
+        1.0, 0.0, 0.0, 0.0,
+    ];
+
+    /// `LOOKING_ALONG_X`, moved to (0, 0, 64) with a 16:9 aspect ratio.
+    const STANDING_WIDESCREEN: [f32; 16] = [
+        0.0, -0.5625, 0.0, 0.0, //
+        0.0, 0.0, 1.0, -64.0, //
+        0.0, 0.0, 0.0, 0.0, //
+        1.0, 0.0, 0.0, 0.0,
+    ];
+
+    fn assert_near(actual: [f32; 2], expected: [f32; 2]) {
+        assert!(
+            (actual[0] - expected[0]).abs() < 1e-3 && (actual[1] - expected[1]).abs() < 1e-3,
+            "expected {expected:?}, got {actual:?}"
+        );
+    }
+
+    #[test]
+    fn position_ahead_is_at_screen_center() {
+        let screen = world_to_screen(&LOOKING_ALONG_X, [100.0, 0.0, 0.0], WIDTH, HEIGHT);
+
+        assert_near(screen.unwrap(), [WIDTH / 2.0, HEIGHT / 2.0]);
+    }
+
+    #[test]
+    fn edges_of_the_view_map_to_screen_edges() {
+        let right = world_to_screen(&LOOKING_ALONG_X, [100.0, -100.0, 0.0], WIDTH, HEIGHT);
+        let left = world_to_screen(&LOOKING_ALONG_X, [100.0, 100.0, 0.0], WIDTH, HEIGHT);
+        let top = world_to_screen(&LOOKING_ALONG_X, [100.0, 0.0, 100.0], WIDTH, HEIGHT);
+
+        assert_near(right.unwrap(), [WIDTH, HEIGHT / 2.0]);
+        assert_near(left.unwrap(), [0.0, HEIGHT / 2.0]);
+        assert_near(top.unwrap(), [WIDTH / 2.0, 0.0]);
+    }
+
+    #[test]
+    fn translation_and_aspect_ratio_are_applied() {
+        let screen = world_to_screen(&STANDING_WIDESCREEN, [200.0, -100.0, 64.0], WIDTH, HEIGHT);
+
+        // 100 units to the right at a distance of 200 is a quarter of the half width at 16:9
+        assert_near(screen.unwrap(), [WIDTH / 2.0 * (1.0 + 9.0 / 32.0), HEIGHT / 2.0]);
+    }
+
+    #[test]
+    fn positions_behind_the_camera_are_not_projected() {
+        assert_eq!(world_to_screen(&LOOKING_ALONG_X, [-100.0, 0.0, 0.0], WIDTH, HEIGHT), None);
+        assert_eq!(world_to_screen(&LOOKING_ALONG_X, [0.005, 0.0, 0.0], WIDTH, HEIGHT), None);
+    }
+
+    #[test]
+    fn angles_follow_the_game_convention() {
+        assert_near(calculate_angle([0.0; 3], [100.0, 0.0, 0.0]), [0.0, 0.0]);
+        assert_near(calculate_angle([0.0; 3], [0.0, 100.0, 0.0]), [0.0, 90.0]);
+        assert_near(calculate_angle([0.0; 3], [100.0, 0.0, -100.0]), [45.0, 0.0]);
+        assert_near(calculate_angle([0.0, 0.0, 64.0], [-100.0, 0.0, 164.0]), [-45.0, 180.0]);
+    }
+
+    #[test]
+    fn yaw_delta_takes_the_shorter_way_around() {
+        assert_near(angle_delta([0.0, 170.0], [10.0, -170.0]), [10.0, 20.0]);
+        assert_near(angle_delta([0.0, -170.0], [-10.0, 170.0]), [-10.0, -20.0]);
+        assert_near(angle_delta([0.0, 30.0], [0.0, 60.0]), [0.0, 30.0]);
+    }
+}
@@ -0,0 +1,111 @@
+//! Stand-ins for game interfaces, so code that talks to the engine can be unit tested without the
+//! game running.
+//!
+//! Each mock is laid out like the real object: its first field is a pointer to a virtual method
+//! table, so calls made through `#[vfunc]` wrappers dispatch into the mock's own functions.
+
+use super::interfaces::{self, engine_client::EngineClient};
+use crate::common::from_ref;
+
+/// Number of entries in the mocked `EngineClient` vtable.
+const ENGINE_CLIENT_VTABLE_LEN: usize = 64;
+
+/// `EngineClient::get_max_clients` vtable index.
+const GET_MAX_CLIENTS_INDEX: usize = 34;
+
+/// `EngineClient::is_in_game` vtable index.
+const IS_IN_GAME_INDEX: usize = 35;
+
+/// `EngineClient::get_local_player_index` vtable index.
+const GET_LOCAL_PLAYER_INDEX_INDEX: usize = 36;
+
+/// A fake `Source2EngineToClient001` interface.
+#[repr(C)]
+pub struct MockEngineClient {
+    vtable: *const usize,
+
+    /// The value returned by `get_max_clients`.
+    pub max_clients: i32,
+
+    /// The value returned by `is_in_game`.
+    pub in_game: bool,
+
+    /// The value returned by `get_local_player_index`.
+    pub local_player_index: i32,
+}
+
+impl MockEngineClient {
+    /// Creates a mock and installs it as the `engine_client` interface for the current thread.
+    ///
+    /// The mock is leaked so that it lives as long as the `&'static` interface reference.
+    pub fn install(in_game: bool) -> &'static Self {
+        let mut vtable = vec![unimplemented_vfunc as *const () as usize; ENGINE_CLIENT_VTABLE_LEN];
+        vtable[GET_MAX_CLIENTS_INDEX] = get_max_clients as *const () as usize;
+        vtable[IS_IN_GAME_INDEX] = is_in_game as *const () as usize;
+        vtable[GET_LOCAL_PLAYER_INDEX_INDEX] = get_local_player_index as *const () as usize;
+
+        let mock: &'static Self = Box::leak(Box::new(Self {
+            vtable: vtable.leak().as_ptr(),
+            max_clients: 64,
+            in_game,
+            local_player_index: 1,
+        }));
+
+        interfaces::mock_engine_client(mock.as_engine_client());
+
+        mock
+    }
+
+    /// Views the mock as the interface type used by the rest of the cheat.
+    #[must_use]
+    pub fn as_engine_client(&'static self) -> &'static EngineClient {
+        // SAFETY: `EngineClient` has no fields of its own; every access goes through the vtable
+        // pointer stored at the start of the mock.
+        unsafe { &*from_ref(self).cast::<EngineClient>() }
+    }
+}
+
+extern "fastcall" fn get_max_clients(this: *const MockEngineClient) -> i32 {
+    // SAFETY: The vtable is only reachable through a live `MockEngineClient`.
+    unsafe { (*this).max_clients }
+}
+
+extern "fastcall" fn is_in_game(this: *const MockEngineClient) -> bool {
+    // SAFETY: The vtable is only reachable through a live `MockEngineClient`.
+    unsafe { (*this).in_game }
+}
+
+extern "fastcall" fn get_local_player_index(this: *const MockEngineClient) -> i32 {
+    // SAFETY: The vtable is only reachable through a live `MockEngineClient`.
+    unsafe { (*this).local_player_index }
+}
+
+extern "fastcall" fn unimplemented_vfunc(_: *const MockEngineClient) {
+    panic!("called a virtual function that is not mocked");
+}
+
+mod tests {
+    use super::MockEngineClient;
+    use crate::cs2::interfaces;
+
+    #[test]
+    fn dispatches_through_vtable() {
+        MockEngineClient::install(true);
+        assert!(interfaces::engine_client().is_in_game());
+
+        MockEngineClient::install(false);
+        assert!(!interfaces::engine_client().is_in_game());
+    }
+
+    #[test]
+    fn reports_max_clients() {
+        MockEngineClient::install(true);
+        assert_eq!(interfaces::engine_client().get_max_clients(), 64);
+    }
+
+    #[test]
+    fn reports_local_player_index() {
+        MockEngineClient::install(true);
+        assert_eq!(interfaces::engine_client().get_local_player_index(), 1);
+    }
+}
@@ -1,3 +1,11 @@
+pub mod entities;
+pub mod input;
 pub mod interfaces;
+pub mod local_player;
+pub mod math;
+#[cfg(test)]
+pub mod mock;
 pub mod modules;
+pub mod offsets;
+pub mod view_matrix;
 pub use modules::{client, engine2, gameoverlayrenderer64, initialize_modules};
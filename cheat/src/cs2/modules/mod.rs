@@ -1,8 +1,7 @@
 use crate::{common, utils::module_handler};
-use anyhow::bail;
-use common::{c_void, Mutex};
+use anyhow::{bail, ensure, Context};
+use common::{c_void, Mutex, OnceLock};
 
-use once_cell::sync::OnceCell;
 use windows::Win32::Foundation::HMODULE;
 
 /// A `Module` represents a dynamically loaded module.
@@ -13,6 +12,8 @@ use windows::Win32::Foundation::HMODULE;
 /// # Fields
 /// - `name`: The name of the module.
 /// - `handle`: The handle to the loaded module.
+/// - `base`: The address the module is loaded at.
+/// - `size`: The size of the module image in memory.
 #[derive(Clone, Debug)]
 pub struct Module {
     /// The name of the module.
@@ -20,6 +21,12 @@ pub struct Module {
 
     /// The handle to the loaded module.
     handle: HMODULE,
+
+    /// The address the module is loaded at.
+    base: usize,
+
+    /// The size of the module image in memory.
+    size: usize,
 }
 
 impl Module {
@@ -32,8 +39,8 @@ impl Module {
     /// A new `Module` instance.
     ///
     /// # Panics
-    /// This function will panic if the module cannot be loaded.
-    /// The panic occurs if `module_handler::get_module_handle(name)` returns `None`.
+    /// This function will panic if the module cannot be loaded. See `Module::try_new` for a
+    /// non-panicking version.
     ///
     /// # Examples
     /// ```
@@ -41,27 +48,178 @@ impl Module {
     /// ```
     #[must_use]
     pub fn new(name: &'static str) -> Self {
-        let handle = module_handler::get_module_handle(name).expect("failed to get module handle");
-        Self { name, handle }
+        Self::try_new(name).unwrap_or_else(|| panic!("failed to load module {name}"))
     }
 
-    /// Searches for a sequence of bytes in the module.
+    /// Creates a new `Module` for the module with the given name, if it is loaded.
     ///
     /// # Parameters
-    /// - `pattern`: The byte pattern to search for.
+    /// - `name`: The name of the module to load.
     ///
     /// # Returns
-    /// The offset of the pattern if found, otherwise `None`.
+    /// A new `Module` instance, or `None` if `module_handler::get_module_handle(name)` or
+    /// `module_handler::get_module_info(handle)` fails, for example because the game has not
+    /// loaded the module.
+    ///
+    /// # Examples
+    /// ```
+    /// let module = Module::try_new("gameoverlayrenderer64.dll");
+    /// ```
+    #[must_use]
+    pub fn try_new(name: &'static str) -> Option<Self> {
+        let handle = module_handler::get_module_handle(name)?;
+        let info = module_handler::get_module_info(handle)?;
+
+        Some(Self {
+            name,
+            handle,
+            base: info.lpBaseOfDll as usize,
+            size: info.SizeOfImage as usize,
+        })
+    }
+
+    /// Returns the handle to the loaded module.
+    #[must_use]
+    pub const fn handle(&self) -> HMODULE {
+        self.handle
+    }
+
+    /// Returns the address the module is loaded at.
+    #[must_use]
+    pub const fn base_address(&self) -> usize {
+        self.base
+    }
+
+    /// Returns the address one past the end of the module image.
+    #[must_use]
+    pub const fn end_address(&self) -> usize {
+        self.base + self.size
+    }
+
+    /// Checks whether `address` lies within the module image.
     ///
     /// # Examples
     /// ```
-    /// let offset = module.find_seq_of_bytes("pattern").unwrap_or(0);
+    /// let address = module.find_address("48 8B C4 4C 89 48 20 55")?;
+    /// assert!(module.contains(address));
     /// ```
     #[must_use]
-    pub fn find_seq_of_bytes<T>(&self, pattern: &str) -> anyhow::Result<*const T> {
+    pub const fn contains(&self, address: usize) -> bool {
+        self.base_address() <= address && address < self.end_address()
+    }
+
+    /// Searches for a sequence of bytes in the module and casts the match to a typed pointer.
+    ///
+    /// `T` is not checked against the pattern in any way; it is only the type the caller intends
+    /// to read at the matched address.
+    ///
+    /// # Parameters
+    /// - `pattern`: The byte pattern to search for.
+    ///
+    /// # Returns
+    /// A pointer to the first match of the pattern, or an error if it was not found.
+    ///
+    /// # Examples
+    /// ```
+    /// let global = module.find_and_cast::<usize>("48 8B 05 ?? ?? ?? ??")?;
+    /// ```
+    pub fn find_and_cast<T>(&self, pattern: &str) -> anyhow::Result<*const T> {
         module_handler::pattern_search(self.handle, pattern)
     }
 
+    /// Searches for every occurrence of a sequence of bytes in the module and casts the matches to
+    /// typed pointers.
+    ///
+    /// Useful for patterns that occur more than once, which have to be told apart by inspecting the
+    /// code around each match.
+    ///
+    /// # Parameters
+    /// - `pattern`: The byte pattern to search for.
+    ///
+    /// # Returns
+    /// A pointer to every non-overlapping match of the pattern, in ascending order. The list is
+    /// empty if the pattern was not found.
+    ///
+    /// # Errors
+    /// Returns an error if the pattern cannot be parsed or the module cannot be read.
+    ///
+    /// # Examples
+    /// ```
+    /// let matches = module.find_all_and_cast::<u8>("E8 ?? ?? ?? ?? 48 8B D8")?;
+    /// ```
+    pub fn find_all_and_cast<T>(&self, pattern: &str) -> anyhow::Result<Vec<*const T>> {
+        Ok(module_handler::pattern_search_all(self.handle, pattern)?
+            .map(|address| address as *const T)
+            .collect())
+    }
+
+    /// Searches for a sequence of bytes in the module.
+    ///
+    /// # Parameters
+    /// - `pattern`: The byte pattern to search for.
+    ///
+    /// # Returns
+    /// The address of the first match of the pattern, or an error if it was not found.
+    ///
+    /// # Examples
+    /// ```
+    /// let address = module.find_address("48 8B C4 4C 89 48 20 55")?;
+    /// ```
+    pub fn find_address(&self, pattern: &str) -> anyhow::Result<usize> {
+        self.find_and_cast::<u8>(pattern).map(|address| address as usize)
+    }
+
+    /// Follows a relative operand of an instruction in the module, such as the displacement of
+    /// `lea rcx, [rip+offset]`, to the address it refers to.
+    ///
+    /// See `module_handler::resolve_rip_relative` for common instruction layouts.
+    ///
+    /// # Parameters
+    /// - `addr`: The address of the first byte of the instruction, usually a pattern scan result.
+    /// - `offset`: The position of the 4-byte signed displacement within the instruction, such as
+    ///   `3` for `48 8D 0D xx xx xx xx`.
+    /// - `instr_len`: The length of the whole instruction, such as `7` for `48 8D 0D xx xx xx xx`.
+    ///
+    /// # Returns
+    /// The address the operand refers to.
+    ///
+    /// # Errors
+    /// Returns an error if the instruction or its target lies outside of the module image, or the
+    /// displacement cannot be read.
+    ///
+    /// # Examples
+    /// ```
+    /// let instruction = module.find_and_cast::<usize>("48 8B 05 ?? ?? ?? ?? 48 85 C0")?;
+    /// let global = module.follow_relative(instruction, 3, 7)?;
+    /// ```
+    pub fn follow_relative<T>(
+        &self,
+        addr: *const T,
+        offset: usize,
+        instr_len: usize,
+    ) -> anyhow::Result<*const T> {
+        let address = addr as usize;
+
+        ensure!(
+            offset + 4 <= instr_len
+                && self.contains(address)
+                && address + instr_len <= self.end_address(),
+            "instruction at {address:#x} is outside of {}",
+            self.name
+        );
+
+        let target = module_handler::resolve_relative(address, offset, instr_len)
+            .with_context(|| format!("failed to read displacement at {address:#x}"))?;
+
+        ensure!(
+            self.contains(target),
+            "relative target {target:#x} of {address:#x} is outside of {}",
+            self.name
+        );
+
+        Ok(target as *const T)
+    }
+
     /// Retrieves the address of an exported function from the module.
     ///
     /// # Parameters
@@ -106,7 +264,7 @@ impl Module {
     /// let module_name = module.name();
     /// ```
     #[must_use]
-    pub const fn name(&self) -> &str {
+    pub const fn name(&self) -> &'static str {
         self.name
     }
 }
@@ -114,55 +272,70 @@ impl Module {
 /// A global static variable holding the list of initialized modules.
 ///
 /// This variable is initialized only once and protected by a `Mutex` to ensure thread safety.
-static MODULES: OnceCell<Mutex<Vec<Module>>> = OnceCell::new();
+static MODULES: OnceLock<Mutex<Vec<Module>>> = OnceLock::new();
 
 /// Initializes the global `MODULES` with the provided module names.
 ///
+/// Modules that are not loaded are skipped with a warning, so the modules that were found are
+/// still available through their accessors.
+///
 /// # Parameters
-/// - `names`: A slice of module names to initialize.
+/// - `required`: A slice of names of the modules the cheat cannot run without.
+/// - `optional`: A slice of names of the modules only some features need.
 ///
 /// # Returns
 /// A `Result` indicating success or failure. If the initialization fails, it returns an error.
 ///
 /// # Errors
 /// - Returns an error if modules are already initialized.
-/// - Panics if setting the global `MODULES` fails.
-///
-/// # Panics
-/// This function will panic if `MODULES.set(...)` fails or if `MODULES.get()` returns `None`
-/// while trying to access the modules. This can happen if the modules were not properly initialized.
+/// - Returns an error listing the missing modules if any of the required modules could not be
+///   found. Missing optional modules are only logged.
 ///
 /// # Examples
 /// ```no_run
-/// let result = initialize_modules(&["module1.dll", "module2.dll"]);
+/// let result = initialize_modules(&["module1.dll"], &["module2.dll"]);
 /// match result {
-///     Ok(_) => println!("Modules initialized successfully"),
-///     Err(e) => eprintln!("Failed to initialize modules: {:?}", e),
+///     Ok(()) => tracing::info!("modules initialized successfully"),
+///     Err(e) => tracing::error!("failed to initialize modules: {e:#}"),
 /// }
 /// ```
-pub fn initialize_modules(names: &[&'static str]) -> anyhow::Result<()> {
+pub fn initialize_modules(
+    required: &[&'static str],
+    optional: &[&'static str],
+) -> anyhow::Result<()> {
     if MODULES.get().is_some() {
         bail!("modules are already initialized");
     }
 
-    let modules = names
-        .iter()
-        .map(|&name| {
-            let module = Module::new(name);
+    let mut modules = Vec::with_capacity(required.len() + optional.len());
+    let mut missing = Vec::new();
 
-            tracing::info!(
-                "initialized module: {} {:p}",
-                module.name,
-                module.handle.0 as *const c_void
-            );
+    for &name in required.iter().chain(optional) {
+        let Some(module) = Module::try_new(name) else {
+            tracing::warn!("module {name} is not loaded, skipping");
 
-            module
-        })
-        .collect();
+            if required.contains(&name) {
+                missing.push(name);
+            }
 
-    match MODULES.set(Mutex::new(modules)) {
-        Ok(_) => {}
-        Err(e) => bail!("failed to initialize MODULES: {e:?}"),
+            continue;
+        };
+
+        tracing::info!(
+            "initialized module: {} {:p}",
+            module.name,
+            module.handle.0 as *const c_void
+        );
+
+        modules.push(module);
+    }
+
+    if MODULES.set(Mutex::new(modules)).is_err() {
+        bail!("modules are already initialized");
+    }
+
+    if !missing.is_empty() {
+        bail!("missing required modules: {}", missing.join(", "));
     }
 
     Ok(())
@@ -173,30 +346,48 @@ pub fn initialize_modules(names: &[&'static str]) -> anyhow::Result<()> {
 ///
 /// # Arguments
 ///
-/// * `$($name:ident),*` - A list of module names (without the ".dll" extension) for which accessor functions will be generated.
+/// * `$($name:ident = $file_name:literal),*` - A list of accessor names, each mapped to the file name of the module it
+///   returns. The file name is used as is, so modules with other extensions (such as `.so`) work as well.
 ///
 /// # Example
 ///
+/// ```ignore
+/// define_module_accessors!(client = "client.dll", engine2 = "engine2.dll");
+/// ```
 macro_rules! define_module_accessors {
-    ($($name:ident),*) => {
+    ($($name:ident = $file_name:literal),* $(,)?) => {
         $(
-            /// Accessor function for the module.
+            #[doc = concat!("Accessor function for the `", $file_name, "` module.")]
             ///
             /// # Panics
             /// Panics if the module is not initialized or if the module is not found.
             pub fn $name() -> &'static Module {
-                let module_name = concat!(stringify!($name), ".dll");
-                let modules_guard = MODULES.get().expect("modules are not initialized").lock();
-                let module = modules_guard.iter()
-                    .find(|module| module.name() == module_name)
-                    .unwrap_or_else(|| {
-                        panic!("module {} is not found", module_name);
-                    });
-
-                Box::leak(Box::new(module.clone()))
+                paste::paste! { [<try_ $name>]() }
+                    .unwrap_or_else(|| panic!("module {} is not found", $file_name))
+            }
+
+            paste::paste! {
+                #[doc = concat!("Accessor function for the `", $file_name, "` module, which may be missing.")]
+                ///
+                /// Returns `None` if the module was not loaded when the modules were initialized.
+                ///
+                /// # Panics
+                /// Panics if the modules are not initialized.
+                pub fn [<try_ $name>]() -> Option<&'static Module> {
+                    let module_name = $file_name;
+                    let modules_guard = MODULES.get().expect("modules are not initialized").lock();
+                    let module = modules_guard.iter().find(|module| module.name() == module_name)?;
+
+                    Some(Box::leak(Box::new(module.clone())))
+                }
             }
         )*
     };
 }
 
-define_module_accessors!(client, engine2, gameoverlayrenderer64);
+define_module_accessors!(
+    client = "client.dll",
+    engine2 = "engine2.dll",
+    gameoverlayrenderer64 = "gameoverlayrenderer64.dll",
+    tier0 = "tier0.dll",
+);
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Context};
+
+use crate::common::{Mutex, OnceLock};
+
+use super::modules::{self, Module};
+
+/// Declares an offset that is resolved by a pattern scan in `initialize`.
+///
+/// # Parameters
+///
+/// * `$name`: The name the offset is looked up by with `get`.
+/// * `$module_fn`: The accessor in `cs2::modules` of the module to scan, such as `client`.
+/// * `$pattern`: The byte pattern to search for.
+macro_rules! register_offset {
+    ($name:ident, $module_fn:ident, $pattern:literal) => {
+        OffsetDefinition { name: stringify!($name), module: modules::$module_fn, pattern: $pattern }
+    };
+}
+
+/// Every offset resolved at startup. New offsets are added here.
+const OFFSETS: &[OffsetDefinition] = &[
+    register_offset!(
+        present,
+        gameoverlayrenderer64,
+        "48 89 5C 24 ?? 48 89 6C 24 ?? 48 89 74 24 ?? 57 41 56 41 57 48 83 EC 20 41 8B E8"
+    ),
+    register_offset!(
+        resize_buffers,
+        gameoverlayrenderer64,
+        "48 89 5C 24 08 48 89 6C 24 10 48 89 74 24 18 57 41 56 41 57 48 83 EC 30 44"
+    ),
+    register_offset!(create_move, client, "48 8B C4 4C 89 48 20 55"),
+    register_offset!(local_player_controller, client, "48 8B 05 ?? ?? ?? ?? 41 89 BE"),
+    register_offset!(view_matrix, client, "48 8D 0D ?? ?? ?? ?? 48 C1 E0 06"),
+];
+
+/// An offset declared with `register_offset!`.
+struct OffsetDefinition {
+    name: &'static str,
+    module: fn() -> &'static Module,
+    pattern: &'static str,
+}
+
+/// The addresses of the offsets in `OFFSETS` that were found, by name.
+#[derive(Default)]
+pub struct OffsetRegistry {
+    offsets: HashMap<&'static str, usize>,
+}
+
+impl OffsetRegistry {
+    /// Returns the address of the offset with the given name, if it was found.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self.offsets.get(name).copied()
+    }
+}
+
+static OFFSET_REGISTRY: OnceLock<OffsetRegistry> = OnceLock::new();
+
+/// Resolves every offset declared in `OFFSETS` and stores the results for `get`.
+///
+/// All offsets are scanned even if some fail, and every found offset is logged, so a game update
+/// that breaks several patterns shows all of them at once. The found offsets stay available
+/// through `get` either way.
+///
+/// # Errors
+///
+/// Returns an error naming every offset that could not be found, or if the offsets were already
+/// initialized.
+pub fn initialize() -> anyhow::Result<()> {
+    let mut registry = OffsetRegistry::default();
+    let mut missing = Vec::new();
+
+    for offset in OFFSETS {
+        let module = (offset.module)();
+
+        match scan(module, offset.name, offset.pattern) {
+            Ok(address) => {
+                tracing::info!(
+                    "found {} at {}+{:#x}",
+                    offset.name,
+                    module.name(),
+                    address - module.base_address()
+                );
+                registry.offsets.insert(offset.name, address);
+            }
+            Err(e) => {
+                tracing::error!("failed to find {} in {}: {e:#}", offset.name, module.name());
+                missing.push(offset.name);
+            }
+        }
+    }
+
+    if OFFSET_REGISTRY.set(registry).is_err() {
+        bail!("offsets are already initialized");
+    }
+
+    if !missing.is_empty() {
+        bail!("missing offsets: {}", missing.join(", "));
+    }
+
+    Ok(())
+}
+
+/// Returns the address of a registered offset.
+///
+/// # Errors
+///
+/// Returns an error if `initialize` has not run or the offset was not found.
+pub fn get(name: &str) -> anyhow::Result<usize> {
+    OFFSET_REGISTRY
+        .get()
+        .context("offsets are not initialized")?
+        .get(name)
+        .with_context(|| format!("offset {name} was not found"))
+}
+
+/// A pattern that was scanned for at startup, kept so the results can be shared.
+#[derive(Clone, Debug)]
+pub struct ScannedPattern {
+    /// The name the pattern is known by, such as `create_move`.
+    pub name: &'static str,
+    /// The module the pattern was found in.
+    pub module: &'static str,
+    /// The pattern string.
+    pub pattern: &'static str,
+    /// The address the pattern resolved to.
+    pub address: usize,
+    /// The address relative to the module base.
+    pub rva: usize,
+}
+
+/// Every pattern that was successfully scanned for, in scan order.
+pub struct PatternRegistry {
+    patterns: Vec<ScannedPattern>,
+}
+
+impl PatternRegistry {
+    /// Returns the scanned patterns.
+    #[must_use]
+    pub fn patterns(&self) -> &[ScannedPattern] {
+        &self.patterns
+    }
+}
+
+pub static PATTERN_REGISTRY: Mutex<PatternRegistry> =
+    Mutex::new(PatternRegistry { patterns: Vec::new() });
+
+/// Scans `module` for `pattern` and records the result in `PATTERN_REGISTRY` under `name`.
+///
+/// # Parameters
+///
+/// * `module`: The module to scan.
+/// * `name`: The name the pattern is known by, used in the offset dump.
+/// * `pattern`: The byte pattern to search for.
+///
+/// # Returns
+///
+/// * `anyhow::Result<usize>`: The address of the first match.
+///
+/// # Errors
+///
+/// Returns an error if the pattern is invalid or not found.
+pub fn scan(module: &Module, name: &'static str, pattern: &'static str) -> anyhow::Result<usize> {
+    let address = module.find_address(pattern)?;
+
+    PATTERN_REGISTRY.lock().patterns.push(ScannedPattern {
+        name,
+        module: module.name(),
+        pattern,
+        address,
+        rva: address.wrapping_sub(module.base_address()),
+    });
+
+    Ok(address)
+}
+
+/// Logs every entry of `PATTERN_REGISTRY` as `[offset-dump] name @ module+0xRVA`, so exact
+/// offsets can be shared without attaching a debugger.
+pub fn dump() {
+    let registry = PATTERN_REGISTRY.lock();
+
+    for entry in registry.patterns() {
+        tracing::info!(
+            "[offset-dump] {} @ {}+{:#x} (pattern: {})",
+            entry.name,
+            entry.module,
+            entry.rva,
+            entry.pattern
+        );
+    }
+
+    tracing::info!("[offset-dump] {} patterns", registry.patterns().len());
+}
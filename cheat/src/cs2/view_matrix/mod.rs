@@ -0,0 +1,80 @@
+use anyhow::{bail, Context};
+
+use crate::{
+    common::{Mutex, OnceLock},
+    cs2::{self, offsets},
+    utils::memory,
+};
+
+/// Position of the displacement in `lea rcx, [rip+offset]`.
+const DISPLACEMENT_OFFSET: usize = 3;
+
+/// Length of `lea rcx, [rip+offset]`.
+const INSTRUCTION_LENGTH: usize = 7;
+
+/// The address of the view matrix global in `client.dll`.
+static VIEW_MATRIX_BASE: OnceLock<usize> = OnceLock::new();
+
+/// The view matrix as of the last `refresh`.
+static VIEW_MATRIX: Mutex<[f32; 16]> = Mutex::new([0.0; 16]);
+
+/// Resolves the address of the view matrix global from the `view_matrix` offset.
+///
+/// # Errors
+///
+/// Returns an error if the offset was not found, its instruction does not refer to an address in
+/// `client.dll`, or the view matrix was already initialized.
+pub fn initialize() -> anyhow::Result<()> {
+    let instruction = offsets::get("view_matrix")? as *const [f32; 16];
+    let base = cs2::modules::client()
+        .follow_relative(instruction, DISPLACEMENT_OFFSET, INSTRUCTION_LENGTH)
+        .context("failed to resolve the view matrix global")?;
+
+    if VIEW_MATRIX_BASE.set(base as usize).is_err() {
+        bail!("view matrix is already initialized");
+    }
+
+    tracing::debug!("found view matrix at {base:p}");
+
+    Ok(())
+}
+
+/// Reads the game's view matrix as it is right now, one row per array.
+///
+/// # Returns
+///
+/// The matrix, or all zeros if `initialize` has not succeeded or the matrix cannot be read.
+#[must_use]
+pub fn get_current() -> [[f32; 4]; 4] {
+    VIEW_MATRIX_BASE.get().and_then(|&base| memory::read(base)).unwrap_or_default()
+}
+
+/// Checks whether the game has a view matrix to project with.
+///
+/// # Returns
+///
+/// `false` if every element of the current matrix is zero, which is the case on the loading
+/// screen of a match (and while the matrix cannot be read).
+#[must_use]
+pub fn is_valid() -> bool {
+    get_current().iter().flatten().any(|&element| element != 0.0)
+}
+
+/// Copies the game's current view matrix, so `get` returns the same matrix for the whole overlay
+/// frame. Called from `hk_present` before the overlay is drawn.
+///
+/// Keeps the previous matrix if the current one cannot be read.
+pub fn refresh() {
+    if let Some(matrix) = VIEW_MATRIX_BASE.get().and_then(|&base| memory::read(base)) {
+        *VIEW_MATRIX.lock() = matrix;
+    }
+}
+
+/// Returns the row-major world-to-clip matrix as of the last `refresh`, for use with
+/// `cs2::math::world_to_screen`.
+///
+/// The matrix is all zeros until the first `refresh`.
+#[must_use]
+pub fn get() -> [f32; 16] {
+    *VIEW_MATRIX.lock()
+}
@@ -3,12 +3,16 @@ pub mod core;
 pub mod cs2;
 pub mod utils;
 
+/// The function hooking API, re-exported so it can be used without going through `utils`.
+pub use utils::hook_system as hooks;
+
 use common::{c_void, null_mut, Once};
 
 use windows::Win32::{
     Foundation::HMODULE,
     System::{
         Console::AllocConsole,
+        LibraryLoader::DisableThreadLibraryCalls,
         Threading::{CreateThread, THREAD_CREATION_FLAGS},
     },
 };
@@ -24,6 +28,9 @@ use windows::Win32::{
 ///
 /// Returns a `u32` value of 0. This value is not used by the operating system.
 extern "system" fn thread_startup(_: *mut c_void) -> u32 {
+    // A panic on this thread unloads the cheat, while panics inside hooks are only reported
+    core::crash::store_cheat_thread();
+
     match core::bootstrap::initialize() {
         Err(e) => {
             tracing::error!("init failed: {e}");
@@ -58,7 +65,7 @@ extern "system" fn thread_startup(_: *mut c_void) -> u32 {
 /// This function will panic if creating a thread fails.
 #[export_name = "DllMain"]
 pub extern "system" fn dll_main(
-    _module: HMODULE,
+    module: HMODULE,
     reason_for_call: u32,
     _reserved: *mut c_void,
 ) -> i32 {
@@ -68,14 +75,26 @@ pub extern "system" fn dll_main(
             static INIT: Once = Once::new();
 
             INIT.call_once(|| {
-                // Create a thread to initialize the cheat
+                // Keep the module handle, so the cheat can unload itself later
+                core::shutdown::store_module(module);
+
+                // The cheat does not need DLL_THREAD_ATTACH / DLL_THREAD_DETACH notifications, so
+                // skip them for every thread the game creates and destroys
+                // SAFETY: `module` is the handle of this DLL, passed in by the loader.
+                if let Err(e) = unsafe { DisableThreadLibraryCalls(module) } {
+                    tracing::warn!("failed to disable thread library calls: {e}");
+                }
+
                 // SAFETY: AllocConsole is unsafe because it involves system-level operations that can fail.
-                unsafe {
-                    if AllocConsole().is_err() {
-                        return;
-                    }
+                // The cheat works without a console, so a failure (for example because the process
+                // already has one) is not a reason to skip initialization. The warning only reaches
+                // the log if a subscriber is already installed.
+                if let Err(e) = unsafe { AllocConsole() } {
+                    tracing::warn!("failed to allocate console, continuing without one: {e}");
                 }
 
+                // Create a thread to initialize the cheat
+
                 // SAFETY: CreateThread is unsafe because it involves creating a new thread at the OS level.
                 match unsafe {
                     CreateThread(
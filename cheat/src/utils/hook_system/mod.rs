@@ -1,13 +1,20 @@
 use crate::common;
 use anyhow::bail;
+use arrayvec::ArrayVec;
 use common::{c_void, from_mut, null_mut};
-use lazy_static::lazy_static;
 
-use std::{
-    collections::VecDeque,
-    sync::{Arc, Mutex},
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
 };
 
+/// Maximum number of hooks that can be installed at the same time.
+const MAX_HOOKS: usize = 32;
+
+/// Passed to `MH_EnableHook` to enable every created hook at once, as `MH_ALL_HOOKS` in
+/// `MinHook.h`.
+const MH_ALL_HOOKS: *mut c_void = null_mut();
+
 /// Represents a function hook.
 pub struct Hook {
     /// A pointer to the target function to be hooked.
@@ -18,9 +25,10 @@ pub struct Hook {
     original: *mut c_void,
 }
 
-lazy_static! {
-    static ref TARGETS: Arc<Mutex<VecDeque<Hook>>> = Arc::new(Mutex::new(VecDeque::new()));
-}
+static TARGETS: Mutex<ArrayVec<Hook, MAX_HOOKS>> = Mutex::new(ArrayVec::new_const());
+
+/// Whether `MinHook` is initialized, so dropped hooks only call into it while it is.
+static MINHOOK_INITIALIZED: AtomicBool = AtomicBool::new(false);
 
 unsafe impl Send for Hook {}
 
@@ -64,7 +72,8 @@ impl Hook {
     ///
     /// # Returns
     ///
-    /// `true` if the hook was successfully created and enabled, `false` otherwise.
+    /// `true` if the hook was successfully created and enabled, `false` otherwise, including when
+    /// `MAX_HOOKS` hooks are already installed.
     ///
     /// # Panics
     ///
@@ -76,24 +85,151 @@ impl Hook {
             return false;
         };
 
-        let mut hk =
-            Self { target: target.cast_mut(), detour: detour.cast_mut(), original: null_mut() };
+        let Some(hk) = Self::create_unlisted(&targets, target, detour) else {
+            return false;
+        };
+
+        // SAFETY: Enabling the hook with MinHook library.
+        let enable_hook_result = unsafe { minhook_sys::MH_EnableHook(hk.target) };
+
+        if enable_hook_result != 0 {
+            tracing::error!(
+                "failed to enable hook at {:p}: status {enable_hook_result}",
+                hk.target
+            );
+
+            // Dropping `hk` removes the disabled hook again
+            return false;
+        }
+
+        targets.push(hk);
+
+        true
+    }
+
+    /// Creates a hook on a target function without enabling it.
+    ///
+    /// Use this to set up several hooks and enable them together with [`enable_all_hooks`], which
+    /// suspends the game's threads once instead of once per hook.
+    ///
+    /// # Parameters
+    ///
+    /// - `target`: A pointer to the target function.
+    /// - `detour`: A pointer to the detour function.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the hook was created, `false` otherwise, including when `MAX_HOOKS` hooks are
+    /// already installed.
+    #[must_use]
+    pub fn create(target: *const c_void, detour: *const c_void) -> bool {
+        let Ok(mut targets) = TARGETS.lock() else {
+            tracing::error!("failed to lock TARGETS");
+            return false;
+        };
+
+        let Some(hk) = Self::create_unlisted(&targets, target, detour) else {
+            return false;
+        };
+
+        targets.push(hk);
+
+        true
+    }
+
+    /// Creates a disabled hook with `MinHook`, without adding it to `targets`.
+    fn create_unlisted(
+        targets: &ArrayVec<Self, MAX_HOOKS>,
+        target: *const c_void,
+        detour: *const c_void,
+    ) -> Option<Self> {
+        if targets.is_full() {
+            tracing::error!("cannot hook {target:p}: all {MAX_HOOKS} hook slots are in use");
+            return None;
+        }
+
+        let mut original = null_mut();
 
         // SAFETY: Creating the hook with MinHook library.
-        let create_hook_result =
-            unsafe { minhook_sys::MH_CreateHook(hk.target, hk.detour, from_mut(&mut hk.original)) };
+        let create_hook_result = unsafe {
+            minhook_sys::MH_CreateHook(
+                target.cast_mut(),
+                detour.cast_mut(),
+                from_mut(&mut original),
+            )
+        };
+
+        if create_hook_result != 0 {
+            tracing::error!("failed to create hook at {target:p}: status {create_hook_result}");
+            return None;
+        }
+
+        Some(Self { target: target.cast_mut(), detour: detour.cast_mut(), original })
+    }
+
+    /// Disables and removes the hook placed on a target function.
+    ///
+    /// # Parameters
+    ///
+    /// - `target`: A pointer to the target function that was previously passed to [`Hook::hook`].
+    ///
+    /// # Returns
+    ///
+    /// `true` if the hook was found and removed, `false` otherwise.
+    #[must_use]
+    pub fn unhook(target: *const c_void) -> bool {
+        let Ok(mut targets) = TARGETS.lock() else {
+            tracing::error!("failed to lock TARGETS");
+            return false;
+        };
+
+        let Some(index) = targets.iter().position(|hook| hook.target == target.cast_mut()) else {
+            return false;
+        };
+
+        let removed = targets[index].remove();
+
+        if removed {
+            targets.remove(index);
+        }
+
+        removed
+    }
+
+    /// Disables and removes the hook from `MinHook`. Once this succeeded, dropping the hook does
+    /// nothing.
+    ///
+    /// `MH_RemoveHook` disables the hook itself if it is enabled. Calling `MH_DisableHook` first
+    /// would fail with `MH_ERROR_DISABLED` for hooks that were created but never enabled, and
+    /// leave them registered.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the hook was removed, `false` if `MinHook` failed to remove it.
+    fn remove(&mut self) -> bool {
+        // SAFETY: The target was hooked through MinHook by `Hook::create_unlisted`.
+        let removed = unsafe { minhook_sys::MH_RemoveHook(self.target) } == 0;
+
+        if removed {
+            self.target = null_mut();
+        }
+
+        removed
+    }
+}
 
-        if create_hook_result == 0 {
-            // SAFETY: Enabling the hook with MinHook library.
-            unsafe {
-                minhook_sys::MH_EnableHook(hk.target);
-            }
+impl Drop for Hook {
+    /// Removes the hook if it is still installed, so a hook that is dropped without going through
+    /// [`Hook::unhook`] does not keep redirecting its target into a detour that may be gone.
+    fn drop(&mut self) {
+        if self.target.is_null() || !MINHOOK_INITIALIZED.load(Ordering::Acquire) {
+            return;
+        }
 
-            targets.push_back(hk);
+        let target = self.target;
 
-            true
-        } else {
-            false
+        if !self.remove() {
+            tracing::error!("failed to remove hook at {target:p} while dropping it");
         }
     }
 }
@@ -115,15 +251,64 @@ pub fn initialize_minhook() -> anyhow::Result<()> {
     // Safety: We are calling an external C library function that initializes MinHook.
     // The function `MH_Initialize` is expected to return 0 on success and a non-zero value on failure.
     // We assume the library's documentation and contract are correct, and we handle the error accordingly.
-    if unsafe { minhook_sys::MH_Initialize() } != 0 {
-        bail!("failed to initialize MinHook");
+    let status = unsafe { minhook_sys::MH_Initialize() };
+
+    if status != 0 {
+        bail!("MH_Initialize failed with status {status}");
     }
 
+    MINHOOK_INITIALIZED.store(true, Ordering::Release);
+
     tracing::info!("MinHook initialized successfully");
 
     Ok(())
 }
 
+/// Enables every hook created with [`Hook::create`] in a single `MH_EnableHook` call.
+///
+/// # Errors
+///
+/// - Returns an `Err` with the `MinHook` status if the hooks cannot be enabled.
+pub fn enable_all_hooks() -> anyhow::Result<()> {
+    // SAFETY: `MH_ALL_HOOKS` makes MinHook enable the hooks it created, which all point at valid
+    // detours.
+    let status = unsafe { minhook_sys::MH_EnableHook(MH_ALL_HOOKS) };
+
+    if status != 0 {
+        bail!("MH_EnableHook(MH_ALL_HOOKS) failed with status {status}");
+    }
+
+    Ok(())
+}
+
+/// Uninitializes the `MinHook` library, disabling and removing every hook it installed.
+///
+/// The hooks are forgotten as well, since their original functions no longer exist. If the hook
+/// list is locked (for example, when called from a panic inside `Hook::hook`), it is left as is;
+/// the entries no longer call into `MinHook` when dropped either way.
+///
+/// # Errors
+///
+/// - Returns an `Err` if `MinHook` fails to uninitialize, for example when it was never initialized.
+pub fn uninitialize_minhook() -> anyhow::Result<()> {
+    // SAFETY: `MH_Uninitialize` restores all hooked functions and returns 0 on success.
+    let status = unsafe { minhook_sys::MH_Uninitialize() };
+
+    if status != 0 {
+        bail!("MH_Uninitialize failed with status {status}");
+    }
+
+    MINHOOK_INITIALIZED.store(false, Ordering::Release);
+
+    if let Ok(mut targets) = TARGETS.try_lock() {
+        targets.clear();
+    }
+
+    tracing::info!("MinHook uninitialized");
+
+    Ok(())
+}
+
 #[macro_export]
 macro_rules! create_hook {
     ($target_function:ident, $detour_function:ident) => {
@@ -132,8 +317,8 @@ macro_rules! create_hook {
 
         tracing::info!("hooking target function: {target_function:p}");
 
-        if !hook_system::Hook::hook(target_function, detour_function_ptr) {
-            bail!("failed to enable hook");
+        if !$crate::hooks::Hook::create(target_function, detour_function_ptr) {
+            bail!("failed to create hook");
         }
     };
 }
@@ -152,10 +337,26 @@ macro_rules! create_hook {
 /// The macro generates a function named `$fn_name` that takes the same parameters and return type as the original function.
 /// This function retrieves the original function pointer from the specified hook and transmutes it to the appropriate function type.
 /// The original function pointer is then returned.
+///
+/// # Panics
+///
+/// Panics with the hook's name if no hook with `$hook_name` as its detour is installed, for
+/// example when the detour is called before `initialize_hooks` has finished. Debug builds also
+/// assert that the original function pointer is not null.
 #[macro_export]
 macro_rules! get_original_fn {
     ($hook_name:ident, $fn_name:ident, ($($arg:ty),*), $ret:ty) => {
-        // Safety: The `hook_system::Hook::get_proto_original` function is assumed to return a valid function pointer
+        let original_fn_ptr = $crate::hooks::Hook::get_proto_original::<_, *mut std::ffi::c_void>(
+            || $hook_name as *mut std::ffi::c_void,
+        )
+        .expect(concat!("no hook is installed for ", stringify!($hook_name)));
+
+        debug_assert!(
+            !original_fn_ptr.is_null(),
+            concat!("original function of ", stringify!($hook_name), " is null")
+        );
+
+        // Safety: The `hooks::Hook::get_proto_original` function is assumed to return a valid function pointer
         // for the specified hook. The `transmute` operation is safe here because the pointer is expected to be valid
         // and the type of the function signature matches the expected type.
         // The correctness of this operation depends on the implementation of `Hook::get_proto_original` and
@@ -164,7 +365,82 @@ macro_rules! get_original_fn {
             std::mem::transmute::<
                 *mut std::ffi::c_void,
                 extern "system" fn($($arg),*) -> $ret,
-            >(hook_system::Hook::get_proto_original(|| $hook_name as *mut std::ffi::c_void).unwrap())
+            >(original_fn_ptr)
         };
     };
 }
+
+#[cfg(all(test, windows))]
+mod tests {
+    use super::{initialize_minhook, Hook};
+    use arrayvec::ArrayVec;
+    use std::{ffi::c_void, hint::black_box, sync::Once};
+
+    /// Initializes `MinHook` once for all tests, since it fails when initialized twice.
+    fn initialize() {
+        static INITIALIZE: Once = Once::new();
+
+        INITIALIZE.call_once(|| initialize_minhook().expect("failed to initialize MinHook"));
+    }
+
+    #[inline(never)]
+    extern "system" fn target(value: u64) -> u64 {
+        // Enough work to give MinHook room for its jump instruction.
+        let mut result = black_box(value);
+        for i in 0..black_box(4) {
+            result = result.wrapping_mul(31).wrapping_add(i);
+        }
+        result
+    }
+
+    #[inline(never)]
+    extern "system" fn unused_target(value: u64) -> u64 {
+        let mut result = black_box(value);
+        for i in 0..black_box(4) {
+            result = result.wrapping_add(i).wrapping_mul(17);
+        }
+        result
+    }
+
+    extern "system" fn detour(_: u64) -> u64 {
+        1337
+    }
+
+    #[test]
+    fn dropping_a_hook_that_was_never_enabled_removes_it() {
+        initialize();
+
+        let target = unused_target as *const c_void;
+        let detour = detour as *const c_void;
+
+        let hook =
+            Hook::create_unlisted(&ArrayVec::new(), target, detour).expect("failed to create hook");
+        drop(hook);
+
+        // Creating the hook again fails with `MH_ERROR_ALREADY_CREATED` if it is still registered
+        let hook = Hook::create_unlisted(&ArrayVec::new(), target, detour)
+            .expect("hook was not removed when dropped");
+        drop(hook);
+    }
+
+    #[test]
+    fn hook_intercepts_and_unhook_restores() {
+        initialize();
+
+        let call = black_box(target as extern "system" fn(u64) -> u64);
+        let expected = call(7);
+
+        assert!(Hook::hook(target as *const c_void, detour as *const c_void));
+        assert_eq!(call(7), 1337);
+
+        let original = Hook::get_proto_original::<_, *mut c_void>(|| detour as *mut c_void)
+            .expect("original function is not registered");
+        // SAFETY: MinHook's trampoline has the same signature as the target.
+        let original: extern "system" fn(u64) -> u64 = unsafe { std::mem::transmute(original) };
+        assert_eq!(original(7), expected);
+
+        assert!(Hook::unhook(target as *const c_void));
+        assert_eq!(call(7), expected);
+        assert!(!Hook::unhook(target as *const c_void));
+    }
+}
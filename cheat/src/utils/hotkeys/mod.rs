@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use crate::common::Mutex;
+
+/// A function run when its hotkey is pressed.
+type Callback = Arc<dyn Fn() + Send + Sync>;
+
+/// The hotkeys handled by the window procedure hook.
+pub static HOTKEYS: Mutex<HotkeyManager> = Mutex::new(HotkeyManager::new());
+
+/// A registry of keys and the functions to run when they are pressed.
+#[derive(Default)]
+pub struct HotkeyManager {
+    hotkeys: Vec<(u32, Callback)>,
+}
+
+impl HotkeyManager {
+    /// Creates a manager without any hotkeys.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { hotkeys: Vec::new() }
+    }
+
+    /// Runs `callback` whenever the key `vk` is pressed.
+    ///
+    /// # Parameters
+    ///
+    /// * `vk`: The virtual-key code of the key, such as `VK_INSERT`.
+    /// * `callback`: The function to run. It is called on the window thread, so it has to return
+    ///   quickly.
+    pub fn register(&mut self, vk: u32, callback: impl Fn() + Send + Sync + 'static) {
+        self.hotkeys.push((vk, Arc::new(callback)));
+    }
+
+    /// Moves every hotkey bound to `old_vk` to `new_vk`, after the key was changed in the
+    /// settings.
+    pub fn rebind(&mut self, old_vk: u32, new_vk: u32) {
+        for (vk, _) in &mut self.hotkeys {
+            if *vk == old_vk {
+                *vk = new_vk;
+            }
+        }
+    }
+
+    /// Returns the callbacks bound to the key `vk`, in the order they were registered.
+    #[must_use]
+    pub fn callbacks(&self, vk: u32) -> Vec<Callback> {
+        self.hotkeys
+            .iter()
+            .filter(|(key, _)| *key == vk)
+            .map(|(_, callback)| Arc::clone(callback))
+            .collect()
+    }
+}
+
+/// Runs the callbacks of the hotkeys bound to the key `vk`.
+///
+/// The callbacks run after `HOTKEYS` is unlocked, so they may register or rebind hotkeys.
+///
+/// # Returns
+///
+/// `true` if any hotkey is bound to the key.
+pub fn dispatch(vk: u32) -> bool {
+    let callbacks = HOTKEYS.lock().callbacks(vk);
+
+    for callback in &callbacks {
+        callback();
+    }
+
+    !callbacks.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HotkeyManager;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn callbacks_follow_their_key() {
+        let presses = Arc::new(AtomicUsize::new(0));
+        let mut manager = HotkeyManager::new();
+
+        let counter = Arc::clone(&presses);
+        manager.register(0x2D, move || {
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+
+        manager.callbacks(0x2D).iter().for_each(|callback| callback());
+        assert_eq!(presses.load(Ordering::Relaxed), 1);
+        assert!(manager.callbacks(0x24).is_empty());
+
+        manager.rebind(0x2D, 0x24);
+
+        assert!(manager.callbacks(0x2D).is_empty());
+        manager.callbacks(0x24).iter().for_each(|callback| callback());
+        assert_eq!(presses.load(Ordering::Relaxed), 2);
+    }
+}
@@ -0,0 +1,50 @@
+use crate::common::{c_void, size_of, slice};
+use windows::Win32::System::Memory::IsBadReadPtr;
+
+/// Views a counted array in game memory as a slice.
+///
+/// The whole range is checked with `IsBadReadPtr` first, so a stale or garbage pointer yields
+/// `None` instead of an access violation.
+///
+/// # Parameters
+///
+/// * `base_ptr`: A pointer to the first element of the array.
+/// * `count`: The number of elements in the array.
+///
+/// # Returns
+///
+/// * `Option<&'static [T]>`: The elements, or `None` if the pointer is null or misaligned, the
+///   size overflows, or any part of the range is not readable.
+#[must_use]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub fn game_slice<T>(base_ptr: *const T, count: usize) -> Option<&'static [T]> {
+    if base_ptr.is_null() || !base_ptr.is_aligned() {
+        return None;
+    }
+
+    let size = count.checked_mul(size_of::<T>())?;
+
+    // SAFETY: `IsBadReadPtr` only probes the range and does not dereference it on our behalf.
+    if unsafe { IsBadReadPtr(Some(base_ptr.cast::<c_void>()), size) }.as_bool() {
+        return None;
+    }
+
+    // SAFETY: The range is readable and `base_ptr` is non-null and aligned. The `'static` lifetime
+    // is a lie: the game may free or reuse the memory at any time. It holds in practice as long as
+    // the slice is only used within the frame it was created in, so callers must not keep it.
+    Some(unsafe { slice::from_raw_parts(base_ptr, count) })
+}
+
+/// Reads a value of type `T` from game memory.
+///
+/// # Parameters
+///
+/// * `address`: The address to read from.
+///
+/// # Returns
+///
+/// * `Option<T>`: The value, or `None` if the address is not readable (see [`game_slice`]).
+#[must_use]
+pub fn read<T: Copy + 'static>(address: usize) -> Option<T> {
+    game_slice(address as *const T, 1).map(|value| value[0])
+}
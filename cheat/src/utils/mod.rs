@@ -1,7 +1,15 @@
 pub mod hook_system;
+pub mod hotkeys;
+pub mod memory;
 pub mod module_handler;
 pub mod render;
 
+/// The hooking macros, re-exported next to `hook_system` so they can be imported by path instead
+/// of from the crate root, where `#[macro_export]` places them.
+pub use crate::{create_hook, get_original_fn};
+
+use crate::common::from_mut;
+
 use windows::Win32::{
     Foundation::{BOOL, FALSE, HWND, LPARAM, TRUE},
     System::{Console::GetConsoleWindow, Threading::GetCurrentProcessId},
@@ -71,9 +79,9 @@ unsafe extern "system" fn enum_window(window: HWND, lparam: LPARAM) -> BOOL {
 pub fn find_window() -> Option<HWND> {
     let mut hwnd: HWND = Default::default();
 
-    let _ = unsafe {
-        EnumWindows(Some(enum_window), LPARAM(std::ptr::from_mut::<HWND>(&mut hwnd) as isize))
-    };
+    // `enum_window` stops the enumeration once it finds the window, which `EnumWindows` reports as
+    // an error, so the result says nothing about whether the window was found
+    let _ = unsafe { EnumWindows(Some(enum_window), LPARAM(from_mut::<HWND>(&mut hwnd) as isize)) };
 
     if hwnd.0 == 0 {
         None
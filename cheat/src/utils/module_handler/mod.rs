@@ -1,14 +1,18 @@
-use std::num::ParseIntError;
+mod scanner;
 
-use crate::common;
+pub use scanner::{pattern_search_simd, PatternScanner, ScanBackend};
+
+use crate::{common, utils::memory};
 use anyhow::{bail, Context};
 use common::*;
 
 use windows::Win32::{
     Foundation::HMODULE,
     System::{
+        Diagnostics::Debug::{IMAGE_NT_HEADERS64, IMAGE_SCN_MEM_EXECUTE, IMAGE_SECTION_HEADER},
         LibraryLoader::{GetModuleHandleW, GetProcAddress},
         ProcessStatus::{GetModuleInformation, MODULEINFO},
+        SystemServices::IMAGE_DOS_HEADER,
         Threading::GetCurrentProcess,
     },
 };
@@ -112,11 +116,156 @@ pub fn get_module_info(module_handle: HMODULE) -> Option<MODULEINFO> {
     }
 }
 
+/// Collects the executable sections of a loaded module.
+///
+/// This function walks the PE headers of the module image and returns the memory of every section
+/// whose `Characteristics` contain `IMAGE_SCN_MEM_EXECUTE`. Sections such as `.pdata`, `.rdata` or
+/// `.data` are skipped, so pattern scans cannot produce false matches inside them.
+///
+/// # Parameters
+///
+/// * `module_handle`: A handle to the module whose sections should be collected.
+///
+/// # Return Value
+///
+/// Returns a list of `(section_address, section_memory)` pairs, one for each executable section.
+///
+/// # Errors
+///
+/// Returns an error if the module information cannot be obtained or if the PE headers point outside
+/// of the module image.
+pub fn executable_sections(module_handle: HMODULE) -> anyhow::Result<Vec<(usize, &'static [u8])>> {
+    let module_info = get_module_info(module_handle).context("failed to get module info")?;
+
+    let base_address = module_info.lpBaseOfDll as usize;
+    let image_size = usize::try_from(module_info.SizeOfImage)
+        .context("failed to convert `SizeOfImage` to usize")?;
+
+    // SAFETY: The module is loaded, so its image starts with a valid DOS header.
+    let dos_header = unsafe { &*(base_address as *const IMAGE_DOS_HEADER) };
+
+    let nt_headers_offset =
+        usize::try_from(dos_header.e_lfanew).context("invalid `e_lfanew` in DOS header")?;
+
+    if nt_headers_offset + size_of::<IMAGE_NT_HEADERS64>() > image_size {
+        bail!("NT headers are outside of the module image");
+    }
+
+    // SAFETY: The NT headers offset was checked against the image size above.
+    let nt_headers = unsafe { &*((base_address + nt_headers_offset) as *const IMAGE_NT_HEADERS64) };
+
+    // The section table immediately follows the optional header.
+    let section_table_offset = nt_headers_offset
+        + std::mem::offset_of!(IMAGE_NT_HEADERS64, OptionalHeader)
+        + usize::from(nt_headers.FileHeader.SizeOfOptionalHeader);
+    let section_count = usize::from(nt_headers.FileHeader.NumberOfSections);
+
+    if section_table_offset + section_count * size_of::<IMAGE_SECTION_HEADER>() > image_size {
+        bail!("section table is outside of the module image");
+    }
+
+    // SAFETY: The section table bounds were checked against the image size above.
+    let section_headers = unsafe {
+        slice::from_raw_parts(
+            (base_address + section_table_offset) as *const IMAGE_SECTION_HEADER,
+            section_count,
+        )
+    };
+
+    let sections = section_headers
+        .iter()
+        .filter(|section| section.Characteristics.0 & IMAGE_SCN_MEM_EXECUTE.0 != 0)
+        .filter_map(|section| {
+            let offset = section.VirtualAddress as usize;
+            // SAFETY: `Misc` is a union of two `u32` values, so reading either field is sound.
+            let size = unsafe { section.Misc.VirtualSize } as usize;
+
+            if offset + size > image_size {
+                tracing::warn!("skipping section that ends outside of the module image");
+                return None;
+            }
+
+            // SAFETY: The section lies within the loaded module image, as checked above.
+            let memory =
+                unsafe { slice::from_raw_parts((base_address + offset) as *const u8, size) };
+
+            Some((base_address + offset, memory))
+        })
+        .collect();
+
+    Ok(sections)
+}
+
+/// Finds the first occurrence of a parsed pattern in a block of memory.
+///
+/// # Parameters
+///
+/// * `memory`: The bytes to search.
+/// * `pattern`: The pattern bytes, where `None` is a wildcard that matches any byte.
+///
+/// # Return Value
+///
+/// Returns the offset of the first match relative to the start of `memory`, or `None` if the
+/// pattern does not occur. An empty pattern never matches.
+#[must_use]
+pub fn find_pattern(memory: &[u8], pattern: &[Option<u8>]) -> Option<usize> {
+    bm_horspool_search(memory, pattern)
+}
+
+/// Finds the first occurrence of a parsed pattern in `haystack` with the Boyer-Moore-Horspool
+/// algorithm.
+///
+/// On a mismatch, the byte under the last position of the pattern decides how far the pattern can
+/// be moved ahead without skipping a match. The shift for each byte is taken from the non-wildcard
+/// bytes of the pattern. A wildcard matches any byte, so no shift may move past the last wildcard
+/// (excluding the final position); a wildcard right before the end limits every shift to 1.
+///
+/// # Parameters
+///
+/// * `haystack`: The bytes to search.
+/// * `pattern`: The pattern bytes, where `None` is a wildcard that matches any byte.
+///
+/// # Return Value
+///
+/// Returns the offset of the first match relative to the start of `haystack`, or `None` if the
+/// pattern does not occur. An empty pattern never matches.
+#[must_use]
+pub fn bm_horspool_search(haystack: &[u8], pattern: &[Option<u8>]) -> Option<usize> {
+    let last = pattern.len().checked_sub(1)?;
+
+    let max_shift =
+        pattern[..last].iter().rposition(Option::is_none).map_or(pattern.len(), |i| last - i);
+
+    let mut shifts = [max_shift; 256];
+
+    for (i, byte) in pattern[..last].iter().enumerate() {
+        if let Some(byte) = byte {
+            let shift = &mut shifts[usize::from(*byte)];
+            *shift = (*shift).min(last - i);
+        }
+    }
+
+    let mut position = 0;
+
+    while position + last < haystack.len() {
+        let window = &haystack[position..=position + last];
+
+        if pattern.iter().zip(window).rev().all(|(&b, &byte)| b.is_none_or(|b| byte == b)) {
+            return Some(position);
+        }
+
+        position += shifts[usize::from(window[last])];
+    }
+
+    None
+}
+
 /// Searches for a pattern within the memory of a specified module.
 ///
-/// This function uses a simple byte-by-byte comparison to find a pattern within the memory of a module.
+/// This function uses the Boyer-Moore-Horspool algorithm (see `bm_horspool_search`) to find a pattern within
+/// the memory of a module.
 /// The pattern is specified as a space-separated sequence of hexadecimal bytes, with "??" representing
-/// a wildcard that matches any byte.
+/// a wildcard that matches any byte. Only executable sections of the module are scanned.
 ///
 /// # Parameters
 ///
@@ -128,69 +277,214 @@ pub fn get_module_info(module_handle: HMODULE) -> Option<MODULEINFO> {
 ///
 /// # Return Value
 ///
-/// Returns `Some(address_offset)` if the pattern is found within the module's memory.
-/// The `address_offset` is the memory address of the first byte of the pattern, relative to the base
-/// address of the module.
+/// Returns `Ok(address)` if the pattern is found within one of the module's executable sections.
+/// The `address` is the memory address of the first byte of the pattern.
 ///
-/// Returns `None` if the pattern is not found within the module's memory.
+/// # Errors
 ///
-/// # Panics
-///
-/// This function may panic if:
+/// Returns an error if:
 ///
 /// * The `pattern` string contains invalid hexadecimal characters.
-/// * The `pattern` string contains a null byte or other invalid characters for a C string.
-/// * The `address_offset` calculation overflows.
-#[must_use]
+/// * The module's sections cannot be read.
+/// * The pattern is not found in any executable section.
 pub fn pattern_search<T>(module_handle: HMODULE, pattern: &str) -> anyhow::Result<*const T> {
-    // Parse the pattern string into bytes and handle wildcards
-    let parsed_pattern_bytes: Result<Vec<Option<u8>>, ParseIntError> =
-        pattern
-            .split_whitespace()
-            .map(|byte_str| {
-                if byte_str == "??" {
-                    Ok(None)
-                } else {
-                    u8::from_str_radix(byte_str, 16).map(Some)
-                }
-            })
-            .collect();
+    let pattern_bytes =
+        parse_pattern(pattern).with_context(|| format!("failed to parse pattern {pattern:?}"))?;
 
-    // Handle parsing errors and continue if successful
-    let pattern_bytes = parsed_pattern_bytes.context("failed to parse pattern: {err}")?;
+    let sections =
+        executable_sections(module_handle).context("failed to get executable sections")?;
 
-    // Retrieve module information
-    let module_info = get_module_info(module_handle).context("failed to get module info")?;
+    for (section_address, section_memory) in sections {
+        if let Some(offset) = find_pattern(section_memory, &pattern_bytes) {
+            let address =
+                section_address.checked_add(offset).context("address calculation overflowed")?;
 
-    let base_address = module_info.lpBaseOfDll;
-    let size = usize::try_from(module_info.SizeOfImage)
-        .context("failed to convert `SizeOfImage` to usize")?;
+            return Ok(address as *const T);
+        }
+    }
 
-    // SAFETY: Convert base_address to a raw pointer for memory access
-    let module_memory = unsafe {
-        // Ensure the pointer and size are valid before creating a slice
-        slice::from_raw_parts(base_address as *const u8, size)
-    };
+    bail!("pattern not found")
+}
 
-    for i in 0..module_memory.len().saturating_sub(pattern_bytes.len()) {
-        if pattern_bytes
-            .iter()
-            .enumerate()
-            .all(|(j, &b)| b.map_or(true, |b| module_memory[i + j] == b))
-        {
-            let address_offset = (base_address as usize)
-                .checked_add(i)
-                .ok_or_else(|| {
-                    tracing::error!("address calculation overflowed");
-                    None::<usize>
-                })
-                .expect("failed to calculate address");
-
-            return Ok(address_offset as *const T);
+/// Searches for every occurrence of a pattern within the memory of a specified module.
+///
+/// Works like `pattern_search`, but keeps scanning after a match, which helps to tell apart
+/// patterns that occur more than once by inspecting the code around each match. Matches do not
+/// overlap: the search resumes after the end of the previous match.
+///
+/// # Parameters
+///
+/// * `module_handle`: A handle to the module within which to search for the pattern.
+/// * `pattern`: A string representing the pattern to search for. The pattern should be a space-separated
+///   sequence of hexadecimal bytes, with "??" representing a wildcard.
+///
+/// # Return Value
+///
+/// Returns an iterator over the address of every match, in ascending order. The iterator is empty
+/// if the pattern does not occur.
+///
+/// # Errors
+///
+/// Returns an error if the `pattern` string cannot be parsed or the module's sections cannot be
+/// read.
+pub fn pattern_search_all(
+    module_handle: HMODULE,
+    pattern: &str,
+) -> anyhow::Result<impl Iterator<Item = usize>> {
+    let pattern_bytes =
+        parse_pattern(pattern).with_context(|| format!("failed to parse pattern {pattern:?}"))?;
+
+    let sections =
+        executable_sections(module_handle).context("failed to get executable sections")?;
+
+    Ok(sections.into_iter().flat_map(move |(section_address, section_memory)| {
+        find_all_patterns(section_memory, &pattern_bytes)
+            .into_iter()
+            .map(move |offset| section_address + offset)
+    }))
+}
+
+/// Finds every non-overlapping occurrence of a parsed pattern in a block of memory.
+///
+/// # Return Value
+///
+/// Returns the offsets of all matches relative to the start of `memory`, in ascending order.
+#[must_use]
+pub fn find_all_patterns(memory: &[u8], pattern: &[Option<u8>]) -> Vec<usize> {
+    let mut offsets = Vec::new();
+    let mut start = 0;
+
+    while let Some(offset) = memory.get(start..).and_then(|rest| find_pattern(rest, pattern)) {
+        offsets.push(start + offset);
+        start += offset + pattern.len();
+    }
+
+    offsets
+}
+
+/// Size of the signed displacement of a RIP-relative operand, in bytes.
+const RIP_DISPLACEMENT_SIZE: usize = 4;
+
+/// Resolves the target of a RIP-relative instruction, such as `lea rcx, [rip+offset]`.
+///
+/// RIP-relative operands are relative to the end of the instruction, so the target is
+/// `address + instruction_length + displacement`. The 4-byte signed displacement has to be the
+/// last part of the instruction, which holds for the usual pattern scan targets:
+///
+/// | Instruction                 | Encoding                    | `instruction_length` |
+/// |-----------------------------|-----------------------------|----------------------|
+/// | `lea rcx, [rip+offset]`     | `48 8D 0D xx xx xx xx`      | 7                    |
+/// | `mov rax, [rip+offset]`     | `48 8B 05 xx xx xx xx`      | 7                    |
+/// | `mov eax, [rip+offset]`     | `8B 05 xx xx xx xx`         | 6                    |
+/// | `call offset` / `jmp offset`| `E8 xx xx xx xx` / `E9 ...` | 5                    |
+///
+/// For instructions with an immediate after the displacement, such as `cmp byte [rip+offset], 0`,
+/// use `Module::follow_relative`, which takes the position of the displacement explicitly.
+///
+/// # Parameters
+///
+/// * `address`: The address of the first byte of the instruction.
+/// * `instruction_length`: The length of the whole instruction, in bytes.
+///
+/// # Return Value
+///
+/// Returns the address the instruction refers to, or `None` if the displacement cannot be read or
+/// `instruction_length` is shorter than the displacement.
+#[must_use]
+pub fn resolve_rip_relative(address: usize, instruction_length: usize) -> Option<usize> {
+    let displacement_offset = instruction_length.checked_sub(RIP_DISPLACEMENT_SIZE)?;
+
+    resolve_relative(address, displacement_offset, instruction_length)
+}
+
+/// Resolves the target of a relative operand whose displacement starts `displacement_offset` bytes
+/// into the instruction at `address`.
+///
+/// # Return Value
+///
+/// Returns `address + instruction_length + displacement`, or `None` if the displacement cannot be
+/// read or the addition overflows.
+#[must_use]
+pub fn resolve_relative(
+    address: usize,
+    displacement_offset: usize,
+    instruction_length: usize,
+) -> Option<usize> {
+    // Read as bytes, since the displacement is rarely 4-byte aligned
+    let displacement_address = address.checked_add(displacement_offset)?;
+    let displacement = i32::from_le_bytes(memory::read(displacement_address)?);
+
+    address.checked_add(instruction_length)?.checked_add_signed(displacement as isize)
+}
+
+/// An error returned by `parse_pattern`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The pattern contains no bytes.
+    Empty,
+
+    /// A token is neither a hexadecimal byte nor a wildcard.
+    InvalidToken {
+        /// The position of the token in the pattern, counting from 0.
+        index: usize,
+
+        /// The token itself.
+        token: String,
+    },
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "pattern is empty"),
+            Self::InvalidToken { index, token } => {
+                write!(f, "token {index} ({token:?}) is neither a hexadecimal byte nor a wildcard")
+            }
         }
     }
+}
 
-    bail!("pattern not found")
+impl std::error::Error for ParseError {}
+
+/// Parses a pattern string into pattern bytes for `find_pattern`.
+///
+/// The pattern is a space-separated sequence of hexadecimal bytes. Both `??` and the single `?`
+/// used by IDA-style patterns are wildcards, so `48 8B ? 24 ?? 4C` is a valid pattern.
+///
+/// # Parameters
+///
+/// * `pattern`: The pattern string to parse.
+///
+/// # Return Value
+///
+/// Returns the pattern bytes, where `None` is a wildcard that matches any byte.
+///
+/// # Errors
+///
+/// Returns `ParseError::Empty` if the pattern has no tokens, and `ParseError::InvalidToken` for the
+/// first token that is not one or two hexadecimal digits or a wildcard.
+pub fn parse_pattern(pattern: &str) -> Result<Vec<Option<u8>>, ParseError> {
+    let pattern_bytes = pattern
+        .split_whitespace()
+        .enumerate()
+        .map(|(index, token)| match token {
+            "?" | "??" => Ok(None),
+            _ if (1..=2).contains(&token.len())
+                && token.bytes().all(|byte| byte.is_ascii_hexdigit()) =>
+            {
+                u8::from_str_radix(token, 16)
+                    .map(Some)
+                    .map_err(|_| ParseError::InvalidToken { index, token: token.to_owned() })
+            }
+            _ => Err(ParseError::InvalidToken { index, token: token.to_owned() }),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if pattern_bytes.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    Ok(pattern_bytes)
 }
 
 /// Retrieves a pointer to a specific interface from a module.
@@ -245,3 +539,180 @@ pub fn get_interface(module_handle: HMODULE, interface_name: &str) -> Option<*co
     // SAFETY: We assume that `function` is a valid function pointer and `interface_name_cstr` is valid.
     Some(unsafe { function(interface_name_cstr.as_ptr(), null_mut()) as *const usize })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        bm_horspool_search, find_all_patterns, find_pattern, parse_pattern, ParseError, ScanBackend,
+    };
+    use proptest::prelude::*;
+
+    /// Straightforward reference scanner the optimized one is checked against.
+    fn reference_find(memory: &[u8], pattern: &[Option<u8>]) -> Option<usize> {
+        if pattern.is_empty() || pattern.len() > memory.len() {
+            return None;
+        }
+
+        (0..=memory.len() - pattern.len()).find(|&i| {
+            pattern.iter().enumerate().all(|(j, b)| b.is_none_or(|b| memory[i + j] == b))
+        })
+    }
+
+    fn pattern_strategy() -> impl Strategy<Value = Vec<Option<u8>>> {
+        // A small byte alphabet keeps accidental matches frequent enough to be interesting.
+        prop::collection::vec(prop::option::weighted(0.8, 0u8..4), 1..8)
+    }
+
+    const MEMORY: [u8; 12] =
+        [0x48, 0x8B, 0xC4, 0x4C, 0x89, 0x48, 0x20, 0x55, 0x48, 0x8B, 0xC4, 0x90];
+
+    #[test]
+    fn finds_pattern_without_wildcards() {
+        assert_eq!(find_pattern(&MEMORY, &[Some(0x4C), Some(0x89), Some(0x48)]), Some(3));
+    }
+
+    #[test]
+    fn finds_first_of_repeated_matches() {
+        assert_eq!(find_pattern(&MEMORY, &[Some(0x48), Some(0x8B), Some(0xC4)]), Some(0));
+    }
+
+    #[test]
+    fn finds_pattern_with_mixed_wildcards() {
+        assert_eq!(
+            find_pattern(&MEMORY, &[Some(0x20), None, Some(0x48), None, Some(0xC4)]),
+            Some(6)
+        );
+    }
+
+    #[test]
+    fn all_wildcards_match_at_start() {
+        assert_eq!(find_pattern(&MEMORY, &[None, None, None]), Some(0));
+    }
+
+    #[test]
+    fn matches_at_the_very_end() {
+        assert_eq!(find_pattern(&MEMORY, &[Some(0xC4), Some(0x90)]), Some(10));
+    }
+
+    #[test]
+    fn missing_pattern_returns_none() {
+        assert_eq!(find_pattern(&MEMORY, &[Some(0xDE), Some(0xAD)]), None);
+        assert_eq!(find_pattern(&MEMORY, &[Some(0x90), Some(0x48)]), None);
+    }
+
+    #[test]
+    fn pattern_longer_than_memory_returns_none() {
+        assert_eq!(find_pattern(&MEMORY[..2], &[Some(0x48), Some(0x8B), Some(0xC4)]), None);
+    }
+
+    #[test]
+    fn empty_pattern_returns_none() {
+        assert_eq!(find_pattern(&MEMORY, &[]), None);
+    }
+
+    #[test]
+    fn parses_mixed_wildcards() {
+        assert_eq!(
+            parse_pattern("48 8B ? 24 ?? 4C"),
+            Ok(vec![Some(0x48), Some(0x8B), None, Some(0x24), None, Some(0x4C)])
+        );
+        assert_eq!(parse_pattern("? ?? ?"), Ok(vec![None, None, None]));
+    }
+
+    #[test]
+    fn parses_single_digit_bytes_and_extra_whitespace() {
+        assert_eq!(parse_pattern("  f  0a \t?? "), Ok(vec![Some(0x0F), Some(0x0A), None]));
+    }
+
+    #[test]
+    fn rejects_empty_pattern() {
+        assert_eq!(parse_pattern(""), Err(ParseError::Empty));
+        assert_eq!(parse_pattern("   "), Err(ParseError::Empty));
+    }
+
+    #[test]
+    fn rejects_invalid_tokens() {
+        for (pattern, index, token) in
+            [("48 ??? 4C", 1, "???"), ("48 GG", 1, "GG"), ("+F", 0, "+F"), ("48 8B4C", 1, "8B4C")]
+        {
+            assert_eq!(
+                parse_pattern(pattern),
+                Err(ParseError::InvalidToken { index, token: token.to_owned() })
+            );
+        }
+    }
+
+    #[test]
+    fn finds_all_non_overlapping_matches() {
+        assert_eq!(find_all_patterns(&MEMORY, &[Some(0x48), Some(0x8B), Some(0xC4)]), [0, 8]);
+        assert_eq!(find_all_patterns(&[0xAA; 5], &[Some(0xAA), Some(0xAA)]), [0, 2]);
+        assert!(find_all_patterns(&MEMORY, &[Some(0xDE), Some(0xAD)]).is_empty());
+    }
+
+    #[test]
+    fn horspool_does_not_skip_past_wildcard_before_last_byte() {
+        // The wildcard right before the last byte limits every shift to 1
+        let memory = [0x01, 0x02, 0x03, 0x04, 0x05];
+        assert_eq!(bm_horspool_search(&memory, &[Some(0x03), None, Some(0x05)]), Some(2));
+        assert_eq!(bm_horspool_search(&memory, &[None, Some(0x04)]), Some(2));
+    }
+
+    #[test]
+    fn horspool_uses_rightmost_occurrence_for_shift() {
+        let memory = [0xAA, 0xAA, 0xAA, 0xBB, 0xAA, 0xCC];
+        assert_eq!(bm_horspool_search(&memory, &[Some(0xAA), Some(0xBB), Some(0xAA)]), Some(2));
+        assert_eq!(bm_horspool_search(&memory, &[Some(0xBB), Some(0xAA), Some(0xCC)]), Some(3));
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference_on_random_input(
+            memory in prop::collection::vec(0u8..4, 0..256),
+            pattern in pattern_strategy(),
+        ) {
+            prop_assert_eq!(find_pattern(&memory, &pattern), reference_find(&memory, &pattern));
+        }
+
+        #[test]
+        fn horspool_matches_reference_on_random_input(
+            memory in prop::collection::vec(0u8..4, 0..512),
+            pattern in prop::collection::vec(prop::option::weighted(0.9, 0u8..4), 1..16),
+        ) {
+            prop_assert_eq!(
+                bm_horspool_search(&memory, &pattern),
+                reference_find(&memory, &pattern)
+            );
+        }
+
+        #[test]
+        fn scan_backends_match_reference_on_random_input(
+            memory in prop::collection::vec(0u8..4, 0..512),
+            pattern in prop::collection::vec(prop::option::weighted(0.7, 0u8..4), 1..16),
+        ) {
+            let expected = reference_find(&memory, &pattern);
+
+            prop_assert_eq!(ScanBackend::Scalar.find(&memory, &pattern), expected);
+            prop_assert_eq!(ScanBackend::Avx2.find(&memory, &pattern), expected);
+        }
+
+        #[test]
+        fn finds_planted_pattern(
+            mut memory in prop::collection::vec(any::<u8>(), 16..256),
+            pattern in pattern_strategy(),
+            position in any::<prop::sample::Index>(),
+        ) {
+            let position = position.index(memory.len() - pattern.len() + 1);
+
+            for (byte, b) in memory[position..].iter_mut().zip(&pattern) {
+                if let Some(b) = b {
+                    *byte = *b;
+                }
+            }
+
+            let found = find_pattern(&memory, &pattern);
+
+            prop_assert_eq!(found, reference_find(&memory, &pattern));
+            prop_assert!(found.is_some_and(|offset| offset <= position));
+        }
+    }
+}
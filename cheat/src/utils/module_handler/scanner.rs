@@ -0,0 +1,192 @@
+use anyhow::{bail, Context};
+use windows::Win32::Foundation::HMODULE;
+
+use crate::cs2::modules::Module;
+
+use super::{bm_horspool_search, executable_sections, parse_pattern};
+
+/// The implementation used to scan memory for a pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScanBackend {
+    /// Boyer-Moore-Horspool, see `bm_horspool_search`.
+    Scalar,
+
+    /// Compares the first concrete byte of the pattern against 32 bytes of memory at once with
+    /// AVX2, and checks the rest of the pattern at every candidate.
+    Avx2,
+}
+
+impl ScanBackend {
+    /// Returns the fastest backend supported by the CPU.
+    #[must_use]
+    pub fn detect() -> Self {
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx2") {
+            return Self::Avx2;
+        }
+
+        Self::Scalar
+    }
+
+    /// Finds the first occurrence of a parsed pattern in a block of memory.
+    ///
+    /// If the CPU does not support the backend, the scalar backend is used instead.
+    ///
+    /// # Parameters
+    ///
+    /// * `memory`: The bytes to search.
+    /// * `pattern`: The pattern bytes, where `None` is a wildcard that matches any byte.
+    ///
+    /// # Return Value
+    ///
+    /// Returns the offset of the first match relative to the start of `memory`, or `None` if the
+    /// pattern does not occur. An empty pattern never matches.
+    #[must_use]
+    pub fn find(self, memory: &[u8], pattern: &[Option<u8>]) -> Option<usize> {
+        match self {
+            #[cfg(target_arch = "x86_64")]
+            Self::Avx2 if is_x86_feature_detected!("avx2") => {
+                // SAFETY: The CPU supports AVX2, as checked above.
+                unsafe { find_pattern_avx2(memory, pattern) }
+            }
+            _ => bm_horspool_search(memory, pattern),
+        }
+    }
+}
+
+/// Scans a module for patterns, with the module's executable sections and the scan backend
+/// looked up once up front.
+pub struct PatternScanner {
+    /// The executable sections of the module, as `(section_address, section_memory)` pairs.
+    sections: Vec<(usize, &'static [u8])>,
+
+    /// The backend used for every scan.
+    backend: ScanBackend,
+}
+
+impl PatternScanner {
+    /// Creates a scanner for `module`, using the fastest backend supported by the CPU.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the executable sections of the module cannot be read.
+    pub fn new(module: &Module) -> anyhow::Result<Self> {
+        Self::from_handle(module.handle())
+    }
+
+    fn from_handle(module_handle: HMODULE) -> anyhow::Result<Self> {
+        let sections =
+            executable_sections(module_handle).context("failed to get executable sections")?;
+
+        Ok(Self { sections, backend: ScanBackend::detect() })
+    }
+
+    /// Returns the backend the scanner uses.
+    #[must_use]
+    pub const fn backend(&self) -> ScanBackend {
+        self.backend
+    }
+
+    /// Searches the module for a pattern.
+    ///
+    /// # Parameters
+    ///
+    /// * `pattern`: A space-separated sequence of hexadecimal bytes, with "??" representing a
+    ///   wildcard.
+    ///
+    /// # Return Value
+    ///
+    /// Returns `Ok(address)` with the address of the first byte of the first match.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pattern cannot be parsed or is not found in any executable section.
+    pub fn scan(&self, pattern: &str) -> anyhow::Result<usize> {
+        let pattern_bytes = parse_pattern(pattern)
+            .with_context(|| format!("failed to parse pattern {pattern:?}"))?;
+
+        for &(section_address, section_memory) in &self.sections {
+            if let Some(offset) = self.backend.find(section_memory, &pattern_bytes) {
+                return section_address
+                    .checked_add(offset)
+                    .context("address calculation overflowed");
+            }
+        }
+
+        bail!("pattern not found")
+    }
+}
+
+/// Searches for a pattern within the memory of a specified module, using the fastest scan backend
+/// supported by the CPU.
+///
+/// Behaves like `pattern_search`. To scan the same module for several patterns, create a
+/// `PatternScanner` once instead.
+///
+/// # Errors
+///
+/// Returns an error if the module's sections cannot be read, or if the pattern cannot be parsed or
+/// is not found in any executable section.
+pub fn pattern_search_simd(module_handle: HMODULE, pattern: &str) -> anyhow::Result<usize> {
+    PatternScanner::from_handle(module_handle)?.scan(pattern)
+}
+
+/// AVX2 implementation of `ScanBackend::find`.
+///
+/// Every position of the first concrete byte of the pattern is found 32 bytes at a time, and the
+/// whole pattern is compared at each of them. Positions near the end of `memory` that do not fill
+/// a whole 32-byte block are checked one by one.
+///
+/// # Safety
+///
+/// The CPU must support AVX2.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn find_pattern_avx2(memory: &[u8], pattern: &[Option<u8>]) -> Option<usize> {
+    use std::arch::x86_64::{
+        __m256i, _mm256_cmpeq_epi8, _mm256_loadu_si256, _mm256_movemask_epi8, _mm256_set1_epi8,
+    };
+
+    const BLOCK: usize = 32;
+
+    let last_start = memory.len().checked_sub(pattern.len())?;
+
+    // A pattern of only wildcards matches at the start
+    let Some((anchor, first)) =
+        pattern.iter().enumerate().find_map(|(index, byte)| byte.map(|byte| (index, byte)))
+    else {
+        return (!pattern.is_empty()).then_some(0);
+    };
+
+    let matches_at = |start: usize| {
+        pattern.iter().zip(&memory[start..]).all(|(&b, &byte)| b.is_none_or(|b| byte == b))
+    };
+
+    let needle = _mm256_set1_epi8(i8::from_ne_bytes([first]));
+    let mut start = 0;
+
+    while start <= last_start && start + anchor + BLOCK <= memory.len() {
+        // SAFETY: `start + anchor + BLOCK` is within `memory`, as checked by the loop condition.
+        let block =
+            unsafe { _mm256_loadu_si256(memory.as_ptr().add(start + anchor).cast::<__m256i>()) };
+        let mut mask = _mm256_movemask_epi8(_mm256_cmpeq_epi8(block, needle)) as u32;
+
+        while mask != 0 {
+            let candidate = start + mask.trailing_zeros() as usize;
+
+            if candidate > last_start {
+                return None;
+            }
+
+            if matches_at(candidate) {
+                return Some(candidate);
+            }
+
+            mask &= mask - 1;
+        }
+
+        start += BLOCK;
+    }
+
+    (start..=last_start).find(|&candidate| matches_at(candidate))
+}
@@ -0,0 +1,93 @@
+use anyhow::Context;
+
+use windows::{
+    core::ComInterface,
+    Win32::Graphics::Dxgi::{
+        CreateDXGIFactory1, IDXGIAdapter1, IDXGIDevice, IDXGIFactory1, IDXGISwapChain,
+        DXGI_ADAPTER_DESC1, DXGI_ADAPTER_FLAG_SOFTWARE,
+    },
+};
+
+/// Adapters with less dedicated video memory than this are treated as integrated GPUs.
+///
+/// DXGI does not report whether an adapter is integrated, but integrated GPUs only reserve a small
+/// carve-out of system memory, while discrete cards report their full VRAM.
+const INTEGRATED_VRAM_THRESHOLD_MB: u64 = 512;
+
+/// Information about a graphics adapter.
+#[derive(Clone, Debug)]
+pub struct AdapterInfo {
+    /// The adapter name reported by the driver.
+    pub description: String,
+    /// Dedicated video memory, in megabytes.
+    pub vram_mb: u64,
+    /// Whether the adapter looks like an integrated or software GPU.
+    pub is_integrated: bool,
+    /// The number of outputs (monitors) attached to the adapter.
+    pub outputs: u32,
+}
+
+impl AdapterInfo {
+    fn from_adapter(adapter: &IDXGIAdapter1) -> anyhow::Result<Self> {
+        let mut desc = DXGI_ADAPTER_DESC1::default();
+
+        // SAFETY: `desc` is a valid, writable adapter description.
+        unsafe { adapter.GetDesc1(&mut desc) }.context("GetDesc1 failed")?;
+
+        let description_len =
+            desc.Description.iter().position(|&c| c == 0).unwrap_or(desc.Description.len());
+        let vram_mb = (desc.DedicatedVideoMemory / (1024 * 1024)) as u64;
+        let is_software = desc.Flags & DXGI_ADAPTER_FLAG_SOFTWARE.0 != 0;
+
+        Ok(Self {
+            description: String::from_utf16_lossy(&desc.Description[..description_len]),
+            vram_mb,
+            is_integrated: is_software || vram_mb < INTEGRATED_VRAM_THRESHOLD_MB,
+            outputs: count_outputs(adapter),
+        })
+    }
+}
+
+fn count_outputs(adapter: &IDXGIAdapter1) -> u32 {
+    // SAFETY: `EnumOutputs` fails with `DXGI_ERROR_NOT_FOUND` once the index is out of range.
+    (0..).take_while(|&index| unsafe { adapter.EnumOutputs(index) }.is_ok()).count() as u32
+}
+
+/// Lists every graphics adapter in the system along with its outputs.
+///
+/// Adapters whose description cannot be read are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the DXGI factory cannot be created.
+pub fn enumerate_adapters() -> anyhow::Result<Vec<AdapterInfo>> {
+    // SAFETY: Creating a DXGI factory has no preconditions.
+    let factory: IDXGIFactory1 =
+        unsafe { CreateDXGIFactory1() }.context("CreateDXGIFactory1 failed")?;
+
+    // SAFETY: `EnumAdapters1` fails with `DXGI_ERROR_NOT_FOUND` once the index is out of range.
+    let adapters = (0..)
+        .map_while(|index| unsafe { factory.EnumAdapters1(index) }.ok())
+        .filter_map(|adapter| {
+            AdapterInfo::from_adapter(&adapter)
+                .inspect_err(|e| tracing::warn!("failed to read adapter description: {e}"))
+                .ok()
+        })
+        .collect();
+
+    Ok(adapters)
+}
+
+/// Returns the adapter that the game's swap chain is rendering on.
+///
+/// # Errors
+///
+/// Returns an error if the device or adapter behind the swap chain cannot be queried.
+pub fn swapchain_adapter(swapchain: &IDXGISwapChain) -> anyhow::Result<AdapterInfo> {
+    // SAFETY: The swap chain is alive for the duration of the present call.
+    let device: IDXGIDevice = unsafe { swapchain.GetDevice() }.context("GetDevice failed")?;
+    // SAFETY: The device was just obtained from a live swap chain.
+    let adapter = unsafe { device.GetAdapter() }.context("GetAdapter failed")?;
+
+    AdapterInfo::from_adapter(&adapter.cast().context("adapter is not an IDXGIAdapter1")?)
+}
@@ -0,0 +1,181 @@
+use crate::common;
+use common::{c_void, from_mut, size_of, Mutex};
+
+use anyhow::Context;
+
+use windows::{
+    core::Interface,
+    Win32::{
+        Foundation::S_OK,
+        Graphics::{
+            Direct3D11::{
+                ID3D11Device, ID3D11DeviceContext, ID3D11Query, D3D11_ASYNC_GETDATA_DONOTFLUSH,
+                D3D11_QUERY, D3D11_QUERY_DATA_TIMESTAMP_DISJOINT, D3D11_QUERY_DESC,
+                D3D11_QUERY_TIMESTAMP, D3D11_QUERY_TIMESTAMP_DISJOINT,
+            },
+            Dxgi::IDXGISwapChain,
+        },
+    },
+};
+
+use super::FRAME_STATS;
+
+/// GPU time above which a frame is reported in the log, in milliseconds.
+const SLOW_GPU_FRAME_MS: f32 = 0.5;
+
+static GPU_TIMER: Mutex<Option<GpuTimer>> = Mutex::new(None);
+
+/// Measures how long the GPU spends on a block of draw calls using timestamp queries.
+///
+/// Query results only become available once the GPU has caught up, which is usually a frame later.
+/// A new measurement is not started until the previous one has been resolved.
+pub struct GpuTimer {
+    disjoint: ID3D11Query,
+    start: ID3D11Query,
+    end: ID3D11Query,
+    pending: bool,
+}
+
+impl GpuTimer {
+    /// Creates the queries used by the timer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the queries cannot be created.
+    pub fn new(device: &ID3D11Device) -> anyhow::Result<Self> {
+        Ok(Self {
+            disjoint: create_query(device, D3D11_QUERY_TIMESTAMP_DISJOINT)?,
+            start: create_query(device, D3D11_QUERY_TIMESTAMP)?,
+            end: create_query(device, D3D11_QUERY_TIMESTAMP)?,
+            pending: false,
+        })
+    }
+
+    /// Starts a measurement.
+    ///
+    /// Returns `false` without doing anything if the previous measurement is still in flight.
+    pub fn begin(&mut self, context: &ID3D11DeviceContext) -> bool {
+        if self.pending {
+            return false;
+        }
+
+        // SAFETY: The queries were created on the device that owns `context`.
+        unsafe {
+            context.Begin(&self.disjoint);
+            context.End(&self.start);
+        }
+
+        true
+    }
+
+    /// Ends the measurement started by [`GpuTimer::begin`].
+    pub fn end(&mut self, context: &ID3D11DeviceContext) {
+        // SAFETY: The queries were created on the device that owns `context`.
+        unsafe {
+            context.End(&self.end);
+            context.End(&self.disjoint);
+        }
+
+        self.pending = true;
+    }
+
+    /// Returns the GPU time of the last measurement in milliseconds once the GPU has finished it.
+    ///
+    /// Returns `None` while the result is not available yet, or when the measurement was
+    /// invalidated by a disjoint event (for example a GPU clock change).
+    pub fn resolve(&mut self, context: &ID3D11DeviceContext) -> Option<f32> {
+        if !self.pending {
+            return None;
+        }
+
+        let disjoint = query_data::<D3D11_QUERY_DATA_TIMESTAMP_DISJOINT>(context, &self.disjoint)?;
+        let start = query_data::<u64>(context, &self.start)?;
+        let end = query_data::<u64>(context, &self.end)?;
+
+        self.pending = false;
+
+        if disjoint.Disjoint.as_bool() || disjoint.Frequency == 0 {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        Some(end.saturating_sub(start) as f32 * 1000.0 / disjoint.Frequency as f32)
+    }
+}
+
+fn create_query(device: &ID3D11Device, query: D3D11_QUERY) -> anyhow::Result<ID3D11Query> {
+    let description = D3D11_QUERY_DESC { Query: query, MiscFlags: 0 };
+    let mut result = None;
+
+    // SAFETY: `description` is a valid query description and `result` outlives the call.
+    unsafe { device.CreateQuery(&description, Some(&mut result)) }.context("CreateQuery failed")?;
+
+    result.context("CreateQuery returned no query")
+}
+
+/// Reads the result of a query without flushing the command buffer.
+///
+/// `ID3D11DeviceContext::GetData` reports a result that is not ready yet with `S_FALSE`, which the
+/// generated wrapper treats as success, so the vtable entry is called directly to tell both apart.
+fn query_data<T: Default>(context: &ID3D11DeviceContext, query: &ID3D11Query) -> Option<T> {
+    let mut data = T::default();
+    let size = u32::try_from(size_of::<T>()).ok()?;
+
+    // SAFETY: `data` is valid for `size` bytes and matches the layout the query writes.
+    let result = unsafe {
+        (context.vtable().GetData)(
+            context.as_raw(),
+            query.as_raw(),
+            from_mut(&mut data).cast::<c_void>(),
+            size,
+            D3D11_ASYNC_GETDATA_DONOTFLUSH.0 as u32,
+        )
+    };
+
+    (result == S_OK).then_some(data)
+}
+
+/// Runs `draw` between GPU timestamp queries and records the resolved time in `FRAME_STATS`.
+///
+/// Timing is skipped, and `draw` is simply called, if the device or the queries are unavailable.
+pub fn time_gpu<R>(swapchain: &IDXGISwapChain, draw: impl FnOnce() -> R) -> R {
+    // SAFETY: The swap chain is alive for the duration of the present call.
+    let device = unsafe { swapchain.GetDevice::<ID3D11Device>() };
+    // SAFETY: The device was just obtained from a live swap chain.
+    let Some((device, context)) = device
+        .and_then(|device| unsafe { device.GetImmediateContext() }.map(|context| (device, context)))
+        .inspect_err(|e| tracing::debug!("could not get device for gpu timing: {e}"))
+        .ok()
+    else {
+        return draw();
+    };
+
+    let mut timer = GPU_TIMER.lock();
+
+    if timer.is_none() {
+        *timer = GpuTimer::new(&device)
+            .inspect_err(|e| tracing::warn!("failed to create gpu timer: {e}"))
+            .ok();
+    }
+
+    let Some(timer) = timer.as_mut() else {
+        return draw();
+    };
+
+    if let Some(gpu_ms) = timer.resolve(&context) {
+        if gpu_ms > SLOW_GPU_FRAME_MS {
+            tracing::debug!("overlay took {gpu_ms:.2} ms on the gpu");
+        }
+
+        FRAME_STATS.lock().gpu_ms = Some(gpu_ms);
+    }
+
+    let started = timer.begin(&context);
+    let result = draw();
+
+    if started {
+        timer.end(&context);
+    }
+
+    result
+}
@@ -1,22 +1,33 @@
 use crate::{
     common,
-    core::{settings, ui},
+    core::{
+        features,
+        settings::{self, Settings},
+        stats, ui,
+    },
 };
 
 use common::{Mutex, OnceLock};
 
 use anyhow::Context;
 use egui_directx11::DirectX11Renderer;
+use std::{
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
+    time::Instant,
+};
 
 use windows::Win32::{
-    Foundation::{HMODULE, HWND, TRUE},
+    Foundation::{BOOL, HMODULE, HWND, TRUE},
     Graphics::{
         Direct3D::{D3D_DRIVER_TYPE_NULL, D3D_FEATURE_LEVEL_10_0, D3D_FEATURE_LEVEL_11_1},
         Direct3D11::{
             D3D11CreateDeviceAndSwapChain, D3D11_CREATE_DEVICE_BGRA_SUPPORT, D3D11_SDK_VERSION,
         },
         Dxgi::{
-            Common::{DXGI_FORMAT_R8G8B8A8_UNORM, DXGI_MODE_DESC, DXGI_SAMPLE_DESC},
+            Common::{
+                DXGI_FORMAT, DXGI_FORMAT_R16G16B16A16_FLOAT, DXGI_FORMAT_R32G32B32A32_FLOAT,
+                DXGI_FORMAT_R8G8B8A8_UNORM, DXGI_MODE_DESC, DXGI_SAMPLE_DESC,
+            },
             IDXGISwapChain, DXGI_SWAP_CHAIN_DESC, DXGI_USAGE_RENDER_TARGET_OUTPUT,
         },
     },
@@ -24,8 +35,27 @@ use windows::Win32::{
 
 use super::{fonts, win32};
 
+pub mod adapters;
+pub mod gpu_timer;
+pub use adapters::{enumerate_adapters, AdapterInfo};
+pub use gpu_timer::GpuTimer;
+
 pub static DX11: OnceLock<Mutex<DirectX11Renderer>> = OnceLock::new();
 
+/// The adapter the game renders on, resolved when the renderer is created.
+pub static ADAPTER: OnceLock<AdapterInfo> = OnceLock::new();
+
+/// Timings of the most recent overlay frame, shown in the debug overlay.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FrameStats {
+    /// CPU time spent building and submitting the overlay, in milliseconds.
+    pub cpu_ms: f32,
+    /// GPU time spent drawing the overlay, in milliseconds, once a measurement has resolved.
+    pub gpu_ms: Option<f32>,
+}
+
+pub static FRAME_STATS: Mutex<FrameStats> = Mutex::new(FrameStats { cpu_ms: 0.0, gpu_ms: None });
+
 /// Creates a DirectX 11 swap chain for the given window handle.
 ///
 /// # Parameters
@@ -78,12 +108,192 @@ fn create_swapchain(window: HWND) -> anyhow::Result<IDXGISwapChain> {
     swapchain.context("could not create d3d11 swapchain")
 }
 
+/// Whether the swap chain was in fullscreen exclusive mode on the last check.
+static FULLSCREEN_EXCLUSIVE: AtomicBool = AtomicBool::new(false);
+
+/// Returns whether the swap chain was in fullscreen exclusive mode on the last present.
+pub fn is_fullscreen_exclusive() -> bool {
+    FULLSCREEN_EXCLUSIVE.load(Ordering::Relaxed)
+}
+
+/// Checks whether the swap chain is in fullscreen exclusive mode and logs when that changes.
+///
+/// In fullscreen exclusive mode the window procedure receives focus and activation messages
+/// differently, and some DX11 operations (such as copying the back buffer into a staging texture)
+/// fail silently. Borderless windowed mode is not affected.
+///
+/// # Parameters
+///
+/// * `swapchain`: A reference to the swap chain being presented.
+///
+/// # Return
+///
+/// * `bool`: `true` if the swap chain is in fullscreen exclusive mode.
+pub fn check_fullscreen_state(swapchain: &IDXGISwapChain) -> bool {
+    let mut fullscreen = BOOL::default();
+
+    // SAFETY: `fullscreen` is a valid, writable `BOOL` and no output is requested.
+    if let Err(e) = unsafe { swapchain.GetFullscreenState(Some(&mut fullscreen), None) } {
+        tracing::debug!("GetFullscreenState failed: {e}");
+        return is_fullscreen_exclusive();
+    }
+
+    let fullscreen = fullscreen.as_bool();
+
+    if FULLSCREEN_EXCLUSIVE.swap(fullscreen, Ordering::Relaxed) != fullscreen {
+        if fullscreen {
+            tracing::warn!(
+                "fullscreen exclusive mode detected, some overlay features may not work"
+            );
+        } else {
+            tracing::info!("swapchain left fullscreen exclusive mode");
+        }
+    }
+
+    fullscreen
+}
+
+/// Brightness of overlay white on HDR back buffers, in nits.
+///
+/// scRGB maps `1.0` to 80 nits, which looks dim next to the rest of an HDR image, so colors are
+/// scaled up to this level instead.
+const PAPER_WHITE_NITS: f32 = 200.0;
+
+/// Returns whether `format` is a floating point format used for HDR (scRGB) output.
+pub fn is_hdr_format(format: DXGI_FORMAT) -> bool {
+    matches!(format, DXGI_FORMAT_R16G16B16A16_FLOAT | DXGI_FORMAT_R32G32B32A32_FLOAT)
+}
+
+/// Reads the back buffer format of the swap chain and configures the renderer for it.
+///
+/// On float HDR back buffers the renderer writes linear colors scaled to [`PAPER_WHITE_NITS`],
+/// since the sRGB output of egui would otherwise look washed out and dim.
+///
+/// # Parameters
+///
+/// * `swapchain`: A reference to the swap chain the renderer draws into.
+/// * `renderer`: The renderer to configure.
+///
+/// # Return
+///
+/// * `Option<DXGI_FORMAT>`: The back buffer format, or `None` if it could not be queried.
+pub fn apply_back_buffer_format(
+    swapchain: &IDXGISwapChain,
+    renderer: &mut DirectX11Renderer,
+) -> Option<DXGI_FORMAT> {
+    let mut desc = DXGI_SWAP_CHAIN_DESC::default();
+
+    // SAFETY: `desc` is a valid, writable swap chain description.
+    if let Err(e) = unsafe { swapchain.GetDesc(&mut desc) } {
+        tracing::warn!("failed to get swapchain description: {e}");
+        return None;
+    }
+
+    let format = desc.BufferDesc.Format;
+    let hdr = is_hdr_format(format);
+    renderer.set_hdr_paper_white(hdr.then_some(PAPER_WHITE_NITS));
+
+    Some(format)
+}
+
+/// Logs the available adapters and remembers the one the game renders on.
+///
+/// A warning is logged if the game runs on an integrated GPU, since the overlay may noticeably
+/// lower the frame rate there.
+fn log_adapters(swapchain: &IDXGISwapChain) {
+    match enumerate_adapters() {
+        Ok(adapters) => {
+            for adapter in adapters {
+                tracing::info!(
+                    "found adapter: {} ({} MB, {} outputs)",
+                    adapter.description,
+                    adapter.vram_mb,
+                    adapter.outputs
+                );
+            }
+        }
+        Err(e) => tracing::warn!("failed to enumerate adapters: {e}"),
+    }
+
+    match adapters::swapchain_adapter(swapchain) {
+        Ok(adapter) => {
+            tracing::info!("game is rendering on {} ({} MB)", adapter.description, adapter.vram_mb);
+
+            if adapter.is_integrated {
+                tracing::warn!(
+                    "game is running on an integrated gpu, overlay performance may suffer"
+                );
+            }
+
+            // The renderer is only created once, so the adapter cannot be set already
+            _ = ADAPTER.set(adapter);
+        }
+        Err(e) => tracing::warn!("failed to get swapchain adapter: {e}"),
+    }
+}
+
+/// Copy of the settings from the last frame that could lock `settings::SETTINGS`.
+///
+/// Frames that find `settings::SETTINGS` locked render from this copy instead of waiting for the
+/// write to finish. Menu changes made on such a frame are discarded.
+static SETTINGS_SNAPSHOT: OnceLock<Mutex<Settings>> = OnceLock::new();
+
+/// Number of consecutive failed attempts to create the renderer after which an error is logged.
+const MAX_INIT_FAILURES: u32 = 10;
+
+/// Consecutive failed attempts to create the renderer.
+static INIT_FAILURES: AtomicU32 = AtomicU32::new(0);
+
+/// Creates the DirectX 11 renderer for the swap chain and configures it for its back buffer.
+///
+/// # Parameters
+///
+/// * `swapchain`: A reference to the DirectX 11 swap chain used for rendering.
+///
+/// # Return
+///
+/// * `anyhow::Result<DirectX11Renderer>`: The renderer, or an error if the swap chain is not ready
+///   yet (for example, when its back buffers have not been allocated).
+fn create_renderer(swapchain: &IDXGISwapChain) -> anyhow::Result<DirectX11Renderer> {
+    let mut renderer = DirectX11Renderer::init_from_swapchain(swapchain, egui::Context::default())
+        .context("could not create dx11 renderer")?;
+
+    if let Some(format) = apply_back_buffer_format(swapchain, &mut renderer) {
+        tracing::info!("back buffer format: {format:?} (hdr: {})", is_hdr_format(format));
+    }
+
+    Ok(renderer)
+}
+
+/// Draws the last overlay frame onto the swap chain again, without building a new one.
+///
+/// Nothing is drawn if the renderer has not been created yet.
+///
+/// # Parameters
+///
+/// * `swapchain`: A reference to the DirectX 11 swap chain used for rendering.
+pub fn repaint(swapchain: &IDXGISwapChain) {
+    let Some(renderer) = DX11.get() else {
+        return;
+    };
+
+    if let Err(e) = renderer.lock().repaint(swapchain) {
+        tracing::warn!("rendering error: {e}");
+    }
+}
+
 /// Initializes the DirectX 11 renderer from the given swap chain.
 ///
 /// This function sets up the DirectX 11 renderer, collects input from the `win32::INPUT` module,
-/// and locks the `settings::SETTINGS` mutex. It then attempts to paint the UI using the provided
-/// closure, which includes setting fonts, modifying tessellation options, and drawing the menu.
-/// If an error occurs during rendering, it logs the error message.
+/// and tries to lock the `settings::SETTINGS` mutex, falling back to a snapshot of the last
+/// settings it could lock so a concurrent settings write never stalls the frame. It then attempts
+/// to paint the UI using the provided closure, which includes setting fonts, modifying
+/// tessellation options, and drawing the menu. If an error occurs during rendering, it logs the
+/// error message.
+///
+/// Creating the renderer fails if this is called before the swap chain's back buffers are
+/// allocated. In that case the frame is skipped and creation is retried on the next call; an error
+/// is logged after `MAX_INIT_FAILURES` consecutive failures.
 ///
 /// # Parameters
 ///
@@ -92,23 +302,38 @@ fn create_swapchain(window: HWND) -> anyhow::Result<IDXGISwapChain> {
 /// # Panics
 ///
 /// This function will panic if:
-/// - The DirectX 11 renderer could not be initialized (`expect("could not create dx11 renderer")`).
 /// - The `win32::INPUT` is not initialized (`expect("win32::INPUT is not initialized")`).
 /// - The input collection failed (`expect("could not collect input")`).
-/// - An error occurs during the `renderer.paint` call (`eprintln!("Rendering error: {e}")`).
 ///
 /// # Return
 ///
 /// This function does not return a value.
 pub fn init_from_swapchain(swapchain: &IDXGISwapChain) {
-    let mut renderer = DX11
-        .get_or_init(|| {
-            Mutex::new(
-                DirectX11Renderer::init_from_swapchain(swapchain, egui::Context::default())
-                    .expect("could not create dx11 renderer"),
-            )
-        })
-        .lock();
+    let renderer = match DX11.get() {
+        Some(renderer) => renderer,
+        None => match create_renderer(swapchain) {
+            Ok(renderer) => {
+                INIT_FAILURES.store(0, Ordering::Relaxed);
+                log_adapters(swapchain);
+                DX11.get_or_init(|| Mutex::new(renderer))
+            }
+            Err(e) => {
+                let failures = INIT_FAILURES.fetch_add(1, Ordering::Relaxed) + 1;
+
+                if failures == MAX_INIT_FAILURES {
+                    tracing::error!(
+                        "failed to create dx11 renderer {failures} times in a row: {e:#}"
+                    );
+                } else {
+                    tracing::debug!("failed to create dx11 renderer, retrying next frame: {e:#}");
+                }
+
+                return;
+            }
+        },
+    };
+
+    let mut renderer = renderer.lock();
 
     let input = win32::INPUT
         .get()
@@ -117,22 +342,47 @@ pub fn init_from_swapchain(swapchain: &IDXGISwapChain) {
         .collect_input()
         .expect("could not collect input");
 
-    let mut settings = settings::SETTINGS.lock();
+    let mut locked = settings::SETTINGS.try_lock();
+    let mut snapshot = SETTINGS_SNAPSHOT.get_or_init(|| Mutex::new(Settings::default())).lock();
 
-    if let Err(e) = renderer.paint(swapchain, &mut settings, input, |ctx, settings| {
-        match fonts::FONTS.lock().as_ref() {
-            Some(fonts) => {
-                ctx.set_fonts(fonts.clone());
-                ctx.tessellation_options_mut(|options| {
-                    options.feathering = false;
-                });
-                ui::draw_menu(ctx, settings);
-            }
-            None => {
-                tracing::warn!("fonts are not set up");
-            }
+    let settings = match locked.as_deref_mut() {
+        Some(settings) => settings,
+        None => {
+            tracing::trace!("settings are locked, rendering from the last snapshot");
+            &mut *snapshot
         }
-    }) {
+    };
+
+    let frame_start = Instant::now();
+
+    let result = gpu_timer::time_gpu(swapchain, || {
+        renderer.paint(swapchain, settings, input, |ctx, settings| {
+            match fonts::FONTS.lock().as_ref() {
+                Some(fonts) => {
+                    ctx.set_fonts(fonts.clone());
+                    ctx.tessellation_options_mut(|options| {
+                        options.feathering = false;
+                    });
+                    ui::draw_menu(ctx, settings);
+                    features::draw(ctx, settings);
+                    ui::draw_debug_overlay(ctx, settings);
+                    ui::draw_bootstrap_status(ctx);
+                }
+                None => {
+                    tracing::warn!("fonts are not set up");
+                }
+            }
+        })
+    });
+
+    FRAME_STATS.lock().cpu_ms = frame_start.elapsed().as_secs_f32() * 1000.0;
+    stats::session_stats().frames_rendered += 1;
+
+    if let Some(locked) = &locked {
+        snapshot.clone_from(locked);
+    }
+
+    if let Err(e) = result {
         tracing::warn!("rendering error: {e}");
     }
 }
@@ -1,22 +1,76 @@
 use crate::common;
 use common::{transmute, Mutex, OnceLock};
 
-use crate::{core::ui, utils::find_window};
+use crate::{
+    core::{crash, debug, ui},
+    cs2::offsets,
+    utils::{find_window, hotkeys},
+};
 use anyhow::{bail, Context};
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    time::Duration,
+};
 
 use egui_win32::InputManager;
 use windows::Win32::{
-    Foundation::{HWND, LPARAM, LRESULT, WPARAM},
-    UI::WindowsAndMessaging::{
-        CallWindowProcW, SetWindowLongPtrA, GWLP_WNDPROC, WM_KEYDOWN, WNDPROC,
+    Foundation::{HINSTANCE, HWND, LPARAM, LRESULT, WPARAM},
+    System::Threading::GetCurrentThreadId,
+    UI::{
+        Input::KeyboardAndMouse::{GetAsyncKeyState, GetKeyState, VK_C, VK_CONTROL, VK_D, VK_O},
+        Shell::{DefSubclassProc, RemoveWindowSubclass, SetWindowSubclass},
+        WindowsAndMessaging::{
+            CallNextHookEx, CallWindowProcW, GetWindowThreadProcessId, PostMessageW,
+            SetWindowLongPtrA, SetWindowsHookExW, UnhookWindowsHookEx, GWLP_WNDPROC, HHOOK,
+            WH_GETMESSAGE, WM_KEYDOWN, WM_NULL, WNDPROC,
+        },
     },
 };
 
-static WNDPROC: OnceLock<WNDPROC> = OnceLock::new();
+/// Identifies our subclass among other subclasses installed on the game window.
+const SUBCLASS_ID: usize = 0x656E_6967;
+
+/// Bit of the `lparam` of `WM_KEYDOWN` that is set if the key was already down, which is the case
+/// for the messages sent while a held key auto-repeats.
+const KEY_REPEAT_BIT: isize = 1 << 30;
+
+/// Set while an entity dump is being written, so that only one is written at a time.
+static DUMPING_ENTITIES: AtomicBool = AtomicBool::new(false);
+
+/// Set while a convar dump is being written, so that only one is written at a time.
+static DUMPING_CONVARS: AtomicBool = AtomicBool::new(false);
+
+/// How long `run_on_window_thread` waits for the window thread to pick up a task.
+const WINDOW_THREAD_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A function waiting to run on the window thread, see `run_on_window_thread`.
+type WindowThreadTask = Box<dyn FnOnce() + Send>;
+
+/// The task the `WH_GETMESSAGE` hook runs on the next message of the window thread.
+static WINDOW_THREAD_TASK: Mutex<Option<WindowThreadTask>> = Mutex::new(None);
+
+/// How the window procedure of the game window was hooked.
+enum WndProcHook {
+    /// Installed with `SetWindowSubclass`, which chains with other subclasses on the window.
+    Subclass,
+    /// Installed with `SetWindowLongPtrA`, holding the replaced window procedure.
+    LongPtr(WNDPROC),
+}
+
+static WNDPROC: OnceLock<WndProcHook> = OnceLock::new();
 pub static INPUT: OnceLock<Mutex<InputManager>> = OnceLock::new();
 
 /// Sets up window procedure hooking and initializes the input manager.
 ///
+/// The window is subclassed with `SetWindowSubclass`, so other tools that hook the window procedure
+/// (overlays, hardware monitors) keep working. Subclassing is only allowed from the thread that owns
+/// the window, so it is done there with `run_on_window_thread`. If that fails, the window procedure
+/// is replaced with `SetWindowLongPtrA` instead.
+///
 /// # Parameters
 ///
 /// - `window`: The handle to the window for which the procedure is set up.
@@ -34,22 +88,45 @@ pub static INPUT: OnceLock<Mutex<InputManager>> = OnceLock::new();
 ///
 /// This function does not panic. However, if the `SetWindowLongPtrA` function fails, it may cause undefined behavior.
 pub fn setup(window: HWND) -> anyhow::Result<()> {
-    // SAFETY:
-    // - `wndproc_hk` is a valid function pointer with the correct signature.
-    // - `SetWindowLongPtrA` expects a pointer to a window procedure, which is provided as `wndproc_hk` cast to `isize`.
-    // - The returned `old_proc_ptr` from `SetWindowLongPtrA` is a valid pointer or `0` if the function fails.
-    #[allow(clippy::fn_to_numeric_cast)]
-    let old_proc_ptr = unsafe { SetWindowLongPtrA(window, GWLP_WNDPROC, wndproc_hk as isize) };
-
-    // SAFETY: The cast to `isize` and back to a function pointer is managed by the API and is safe here.
-    // We use `old_proc_ptr` to verify that the window procedure was successfully set.
-    let wndproc_fn = unsafe {
-        transmute::<isize, Option<unsafe extern "system" fn(HWND, u32, WPARAM, LPARAM) -> LRESULT>>(
-            old_proc_ptr,
-        )
+    if WNDPROC.get().is_some() {
+        bail!("WNDPROC is already initialized");
+    }
+
+    let subclassed = run_on_window_thread(window, move || {
+        // SAFETY: `subclass_proc` is a valid subclass procedure and `SUBCLASS_ID` is unique to us.
+        unsafe { SetWindowSubclass(window, Some(subclass_proc), SUBCLASS_ID, 0) }.as_bool()
+    });
+
+    let hook = if subclassed == Some(true) {
+        WndProcHook::Subclass
+    } else {
+        tracing::warn!("SetWindowSubclass failed, falling back to SetWindowLongPtrA");
+
+        // SAFETY:
+        // - `wndproc_hk` is a valid function pointer with the correct signature.
+        // - `SetWindowLongPtrA` expects a pointer to a window procedure, which is provided as `wndproc_hk` cast to `isize`.
+        // - The returned `old_proc_ptr` from `SetWindowLongPtrA` is a valid pointer or `0` if the function fails.
+        #[allow(clippy::fn_to_numeric_cast)]
+        let old_proc_ptr = unsafe {
+            SetWindowLongPtrA(
+                window,
+                GWLP_WNDPROC,
+                wndproc_hk as unsafe extern "system" fn(HWND, u32, WPARAM, LPARAM) -> LRESULT
+                    as isize,
+            )
+        };
+
+        // SAFETY: The cast to `isize` and back to a function pointer is managed by the API and is safe here.
+        // We use `old_proc_ptr` to verify that the window procedure was successfully set.
+        WndProcHook::LongPtr(unsafe {
+            transmute::<
+                isize,
+                Option<unsafe extern "system" fn(HWND, u32, WPARAM, LPARAM) -> LRESULT>,
+            >(old_proc_ptr)
+        })
     };
 
-    if WNDPROC.set(wndproc_fn).is_err() {
+    if WNDPROC.set(hook).is_err() {
         bail!("WNDPROC is already initialized");
     }
 
@@ -64,7 +141,7 @@ pub fn setup(window: HWND) -> anyhow::Result<()> {
 /// Destroys the input handling and menu system for the application.
 ///
 /// This function retrieves the window handle, checks if the `WNDPROC` and `INPUT` are initialized,
-/// and then restores the original `WNDPROC` to the window.
+/// and then removes the subclass or restores the original `WNDPROC` of the window.
 ///
 /// # Returns
 ///
@@ -73,40 +150,232 @@ pub fn setup(window: HWND) -> anyhow::Result<()> {
 pub fn destroy() -> anyhow::Result<()> {
     let window = find_window().context("could not find window")?;
 
-    let Some(Some(wndproc)) = WNDPROC.get() else {
-        bail!("WNDPROC is not initialized");
-    };
+    match WNDPROC.get() {
+        Some(WndProcHook::Subclass) => {
+            let removed = run_on_window_thread(window, move || {
+                // SAFETY: The subclass was installed on this window by `setup`.
+                unsafe { RemoveWindowSubclass(window, Some(subclass_proc), SUBCLASS_ID) }.as_bool()
+            });
 
-    // SAFETY: The `SetWindowLongPtrA` function is used here to set the window procedure, which requires a valid function pointer.
-    #[allow(clippy::fn_to_numeric_cast)]
-    unsafe {
-        SetWindowLongPtrA(window, GWLP_WNDPROC, *wndproc as isize);
-    };
+            if removed != Some(true) {
+                bail!("failed to remove window subclass");
+            }
+        }
+        Some(WndProcHook::LongPtr(Some(wndproc))) => {
+            // SAFETY: The `SetWindowLongPtrA` function is used here to set the window procedure, which requires a valid function pointer.
+            #[allow(clippy::fn_to_numeric_cast)]
+            unsafe {
+                SetWindowLongPtrA(window, GWLP_WNDPROC, *wndproc as isize);
+            };
+        }
+        _ => bail!("WNDPROC is not initialized"),
+    }
 
     Ok(())
 }
 
-unsafe extern "system" fn wndproc_hk(
+/// Runs `task` on the thread that owns `window` and returns its result.
+///
+/// Window subclasses can only be installed and removed from that thread, while `setup` and
+/// `destroy` run on threads of their own. The task is handed to a `WH_GETMESSAGE` hook on the window
+/// thread, which runs it on the next message the thread retrieves. A `WM_NULL` is posted to the
+/// window so that there is one.
+///
+/// # Returns
+///
+/// `None` if the hook could not be installed, or the window thread did not retrieve a message
+/// within `WINDOW_THREAD_TIMEOUT`. The task has not run and never will in that case.
+fn run_on_window_thread<T: Send + 'static>(
     window: HWND,
-    msg: u32,
-    wparam: WPARAM,
-    lparam: LPARAM,
-) -> LRESULT {
+    task: impl FnOnce() -> T + Send + 'static,
+) -> Option<T> {
+    // SAFETY: `GetWindowThreadProcessId` accepts any window handle and returns 0 if it is invalid.
+    let thread_id = unsafe { GetWindowThreadProcessId(window, None) };
+
+    if thread_id == 0 {
+        return None;
+    }
+
+    // SAFETY: `GetCurrentThreadId` has no preconditions.
+    if thread_id == unsafe { GetCurrentThreadId() } {
+        return Some(task());
+    }
+
+    let (sender, receiver) = mpsc::sync_channel(1);
+
+    *WINDOW_THREAD_TASK.lock() = Some(Box::new(move || {
+        // Once the task was taken, the caller waits for its result, so sending cannot fail
+        let _ = sender.send(task());
+    }));
+
+    // SAFETY: `get_message_hook` is a valid hook procedure. The module handle may be null since the
+    // hook is installed on a thread of this process.
+    let hook = match unsafe {
+        SetWindowsHookExW(WH_GETMESSAGE, Some(get_message_hook), HINSTANCE::default(), thread_id)
+    } {
+        Ok(hook) => hook,
+        Err(e) => {
+            tracing::warn!("failed to hook the window thread: {e}");
+            WINDOW_THREAD_TASK.lock().take();
+            return None;
+        }
+    };
+
+    // SAFETY: `PostMessageW` accepts any window handle, and `WM_NULL` is ignored by the window.
+    if let Err(e) = unsafe { PostMessageW(window, WM_NULL, WPARAM(0), LPARAM(0)) } {
+        tracing::warn!("failed to wake up the window thread: {e}");
+    }
+
+    let result = receiver.recv_timeout(WINDOW_THREAD_TIMEOUT).ok().or_else(|| {
+        // If the hook already took the task, it is running right now, so wait for it to finish
+        // instead of reporting that it did not run
+        WINDOW_THREAD_TASK.lock().take().map_or_else(|| receiver.recv().ok(), |_| None)
+    });
+
+    // SAFETY: The hook was installed above and is removed exactly once.
+    if let Err(e) = unsafe { UnhookWindowsHookEx(hook) } {
+        tracing::warn!("failed to unhook the window thread: {e}");
+    }
+
+    result
+}
+
+/// Runs the task waiting in `WINDOW_THREAD_TASK`, on the window thread.
+unsafe extern "system" fn get_message_hook(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code >= 0 {
+        let task = WINDOW_THREAD_TASK.lock().take();
+
+        if let Some(task) = task {
+            crash::catch_panic("window thread task", task);
+        }
+    }
+
+    CallNextHookEx(HHOOK::default(), code, wparam, lparam)
+}
+
+/// Feeds a window message to the input manager and the menu.
+///
+/// Returns `Some(result)` if the message must not reach the game.
+fn handle_message(msg: u32, wparam: WPARAM, lparam: LPARAM) -> Option<LRESULT> {
     INPUT.get().expect("INPUT is not initialized").lock().process(msg, wparam.0, lparam.0);
 
-    let wndproc = WNDPROC.get().expect("WNDPROC is not initialized");
+    if msg == WM_KEYDOWN {
+        if let Ok(key) = u32::try_from(wparam.0) {
+            hotkeys::dispatch(key);
+        }
+    }
+
+    let is_repeat = lparam.0 & KEY_REPEAT_BIT != 0;
 
     match msg {
-        WM_KEYDOWN if wparam.0 == 0x2D => {
-            ui::toggle_menu(); // Toggle menu visibility
+        WM_KEYDOWN if wparam.0 == usize::from(VK_D.0) && is_ctrl_down() && !is_repeat => {
+            spawn_dump(&DUMPING_ENTITIES, "entities", debug::dump_entities);
+        }
+        WM_KEYDOWN if wparam.0 == usize::from(VK_C.0) && is_ctrl_down() && !is_repeat => {
+            spawn_dump(&DUMPING_CONVARS, "convars", debug::dump_convars);
+        }
+        WM_KEYDOWN if wparam.0 == usize::from(VK_O.0) && is_ctrl_down() => {
+            offsets::dump();
         }
         _ => (),
     }
 
     // Check if the menu is open and block input if necessary
-    if ui::should_block_input(msg) {
-        return LRESULT(1);
+    ui::should_block_input(msg).then_some(LRESULT(1))
+}
+
+/// Writes a dump on a thread of its own, unless the previous dump of the same kind is still being
+/// written.
+///
+/// Dumping reads whole lists from the game, so it is kept off the window thread. The dumps are
+/// named after the current second, so two of them running at once would write the same file.
+///
+/// # Parameters
+///
+/// * `in_progress`: Set while the dump is being written.
+/// * `name`: What is dumped, for the log.
+/// * `dump`: The function that writes the dump.
+fn spawn_dump(
+    in_progress: &'static AtomicBool,
+    name: &'static str,
+    dump: fn() -> anyhow::Result<PathBuf>,
+) {
+    if in_progress.swap(true, Ordering::Acquire) {
+        tracing::debug!("{name} are already being dumped");
+        return;
     }
 
+    std::thread::spawn(move || {
+        if let Err(e) = dump() {
+            tracing::warn!("failed to dump {name}: {e:#}");
+        }
+
+        in_progress.store(false, Ordering::Release);
+    });
+}
+
+/// Returns whether either Ctrl key is held down.
+fn is_ctrl_down() -> bool {
+    // SAFETY: `GetKeyState` has no preconditions.
+    let state = unsafe { GetKeyState(i32::from(VK_CONTROL.0)) };
+
+    // The high bit is set while the key is down
+    state < 0
+}
+
+/// Returns whether a key is held down right now.
+///
+/// Unlike `is_ctrl_down`, this reads the physical key state rather than the state at the last
+/// window message, so it also works from the game thread.
+///
+/// # Parameters
+///
+/// * `key`: The virtual-key code of the key, such as `VK_XBUTTON2`.
+#[must_use]
+pub fn is_key_down(key: u32) -> bool {
+    let Ok(key) = i32::try_from(key) else {
+        return false;
+    };
+
+    // SAFETY: `GetAsyncKeyState` has no preconditions.
+    let state = unsafe { GetAsyncKeyState(key) };
+
+    // The high bit is set while the key is down
+    state < 0
+}
+
+unsafe extern "system" fn subclass_proc(
+    window: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+    _subclass_id: usize,
+    _ref_data: usize,
+) -> LRESULT {
+    if let Some(result) =
+        crash::catch_panic("window message", || handle_message(msg, wparam, lparam)).flatten()
+    {
+        return result;
+    }
+
+    DefSubclassProc(window, msg, wparam, lparam)
+}
+
+unsafe extern "system" fn wndproc_hk(
+    window: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    if let Some(result) =
+        crash::catch_panic("window message", || handle_message(msg, wparam, lparam)).flatten()
+    {
+        return result;
+    }
+
+    let Some(WndProcHook::LongPtr(wndproc)) = WNDPROC.get() else {
+        panic!("WNDPROC is not initialized");
+    };
+
     CallWindowProcW(*wndproc, window, msg, wparam, lparam)
 }
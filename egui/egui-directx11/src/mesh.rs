@@ -15,7 +15,14 @@ pub struct GpuMesh {
 }
 
 impl GpuMesh {
-    pub fn from_mesh((w, h): (f32, f32), mesh: Mesh, scissors: Rect) -> Option<Self> {
+    // `usize::is_multiple_of` needs a newer Rust than the cheat's minimum supported version
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn from_mesh(
+        (w, h): (f32, f32),
+        mesh: Mesh,
+        scissors: Rect,
+        hdr_scale: Option<f32>,
+    ) -> Option<Self> {
         if mesh.indices.is_empty() || mesh.indices.len() % 3 != 0 {
             None
         } else {
@@ -25,7 +32,10 @@ impl GpuMesh {
                 .map(|v| GpuVertex {
                     pos: Pos2::new((v.pos.x - w / 2.) / (w / 2.), (v.pos.y - h / 2.) / -(h / 2.)),
                     uv: v.uv,
-                    color: v.color.into(),
+                    color: match hdr_scale {
+                        Some(scale) => hdr_color(v.color.into(), scale),
+                        None => v.color.into(),
+                    },
                 })
                 .collect();
 
@@ -39,6 +49,30 @@ impl GpuMesh {
     }
 }
 
+/// Scales a linear color for a scRGB back buffer, where `1.0` is 80 nits.
+///
+/// The pixel shader raises the color to `1 / 2.2` for sRGB targets, so the scaled color is raised
+/// to `2.2` here to cancel that out. Vertex colors are premultiplied, so alpha is divided out
+/// before the power and multiplied back in after it; otherwise translucent colors come out too
+/// dark.
+fn hdr_color(color: Rgba, scale: f32) -> Rgba {
+    let [r, g, b, a] = color.to_array();
+    let encode = |c: f32| (c * scale).powf(2.2);
+
+    // Additive colors have no alpha to divide out
+    if a <= 0.0 {
+        return Rgba::from_rgba_premultiplied(encode(r), encode(g), encode(b), a);
+    }
+
+    let encode_premultiplied = |c: f32| encode(c / a) * a;
+    Rgba::from_rgba_premultiplied(
+        encode_premultiplied(r),
+        encode_premultiplied(g),
+        encode_premultiplied(b),
+        a,
+    )
+}
+
 #[repr(C)]
 pub struct GpuVertex {
     pos: Pos2,
@@ -92,3 +126,22 @@ pub fn create_index_buffer(
         output.ok_or(RenderError::General("Failed to create index buffer"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::hdr_color;
+    use egui::Rgba;
+
+    #[test]
+    fn hdr_color_scales_translucent_colors_like_opaque_ones() {
+        let scale = 2.5;
+        let [r, g, b, _] = hdr_color(Rgba::from_rgb(0.5, 0.25, 1.0), scale).to_array();
+        let translucent = hdr_color(Rgba::from_rgba_unmultiplied(0.5, 0.25, 1.0, 0.5), scale);
+
+        for (opaque, translucent) in [r, g, b].into_iter().zip(translucent.to_array()) {
+            assert!((opaque * 0.5 - translucent).abs() < 1e-5, "{opaque} * 0.5 != {translucent}");
+        }
+
+        assert_eq!(translucent.a(), 0.5);
+    }
+}
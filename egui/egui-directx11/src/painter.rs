@@ -47,6 +47,8 @@ pub struct DirectX11Renderer {
     backup: BackupState,
     hwnd: HWND,
     context: egui::Context,
+    hdr_scale: Option<f32>,
+    meshes: Vec<GpuMesh>,
 }
 
 impl DirectX11Renderer {
@@ -121,6 +123,8 @@ impl DirectX11Renderer {
                 shaders,
                 hwnd,
                 context,
+                hdr_scale: None,
+                meshes: Vec::new(),
             })
         }
     }
@@ -149,65 +153,85 @@ impl DirectX11Renderer {
                 self.tex_alloc.process_deltas(dev, ctx, output.textures_delta)?;
             }
 
-            if output.shapes.is_empty() {
-                self.backup.restore(ctx);
-                return Ok(());
-            }
-
-            let primitives = self
+            self.meshes = self
                 .context
                 .tessellate(output.shapes)
                 .into_iter()
                 .filter_map(|prim| {
                     if let Primitive::Mesh(mesh) = prim.primitive {
-                        GpuMesh::from_mesh(screen, mesh, prim.clip_rect)
+                        GpuMesh::from_mesh(screen, mesh, prim.clip_rect, self.hdr_scale)
                     } else {
                         panic!("Paint callbacks are not yet supported")
                     }
                 })
                 .collect::<Vec<_>>();
 
-            self.set_blend_state(dev, ctx)?;
-            self.set_raster_options(dev, ctx)?;
-            self.set_sampler_state(dev, ctx)?;
-
-            ctx.RSSetViewports(Some(&[self.get_viewport()]));
-            ctx.OMSetRenderTargets(Some(&[self.render_view.clone()]), None);
-            ctx.IASetPrimitiveTopology(D3D11_PRIMITIVE_TOPOLOGY_TRIANGLELIST);
-            ctx.IASetInputLayout(&self.input_layout);
-
-            for mesh in primitives {
-                let idx = create_index_buffer(dev, &mesh)?;
-                let vtx = create_vertex_buffer(dev, &mesh)?;
-
-                let texture = self.tex_alloc.get_by_id(mesh.texture_id);
-
-                ctx.RSSetScissorRects(Some(&[RECT {
-                    left: mesh.clip.left() as _,
-                    top: mesh.clip.top() as _,
-                    right: mesh.clip.right() as _,
-                    bottom: mesh.clip.bottom() as _,
-                }]));
-
-                if texture.is_some() {
-                    ctx.PSSetShaderResources(0, Some(&[texture]));
-                }
-
-                ctx.IASetVertexBuffers(
-                    0,
-                    1,
-                    Some(&Some(vtx)),
-                    Some(&(size_of::<GpuVertex>() as _)),
-                    Some(&0),
-                );
-                ctx.IASetIndexBuffer(&idx, DXGI_FORMAT_R32_UINT, 0);
-                ctx.VSSetShader(&self.shaders.vertex, Some(&[]));
-                ctx.PSSetShader(&self.shaders.pixel, Some(&[]));
-
-                ctx.DrawIndexed(mesh.indices.len() as _, 0, 0);
-            }
+            let result = self.draw_meshes(dev, ctx);
+            self.backup.restore(ctx);
+            result
+        }
+    }
 
+    /// Draws the meshes of the last [`Self::paint`] call again, without running egui.
+    /// Call it instead of [`Self::paint`] on presents that should not build a new frame, so the
+    /// overlay stays on screen.
+    pub fn repaint(&self, swap_chain: &IDXGISwapChain) -> Result<(), RenderError> {
+        unsafe {
+            let (dev, ctx) = &get_device_and_context(swap_chain)?;
+            self.backup.save(ctx);
+            let result = self.draw_meshes(dev, ctx);
             self.backup.restore(ctx);
+            result
+        }
+    }
+
+    unsafe fn draw_meshes(
+        &self,
+        dev: &ID3D11Device,
+        ctx: &ID3D11DeviceContext,
+    ) -> Result<(), RenderError> {
+        if self.meshes.is_empty() {
+            return Ok(());
+        }
+
+        self.set_blend_state(dev, ctx)?;
+        self.set_raster_options(dev, ctx)?;
+        self.set_sampler_state(dev, ctx)?;
+
+        ctx.RSSetViewports(Some(&[self.get_viewport()]));
+        ctx.OMSetRenderTargets(Some(std::slice::from_ref(&self.render_view)), None);
+        ctx.IASetPrimitiveTopology(D3D11_PRIMITIVE_TOPOLOGY_TRIANGLELIST);
+        ctx.IASetInputLayout(&self.input_layout);
+
+        for mesh in &self.meshes {
+            let idx = create_index_buffer(dev, mesh)?;
+            let vtx = create_vertex_buffer(dev, mesh)?;
+
+            let texture = self.tex_alloc.get_by_id(mesh.texture_id);
+
+            ctx.RSSetScissorRects(Some(&[RECT {
+                left: mesh.clip.left() as _,
+                top: mesh.clip.top() as _,
+                right: mesh.clip.right() as _,
+                bottom: mesh.clip.bottom() as _,
+            }]));
+
+            if texture.is_some() {
+                ctx.PSSetShaderResources(0, Some(&[texture]));
+            }
+
+            ctx.IASetVertexBuffers(
+                0,
+                1,
+                Some(&Some(vtx)),
+                Some(&(size_of::<GpuVertex>() as _)),
+                Some(&0),
+            );
+            ctx.IASetIndexBuffer(&idx, DXGI_FORMAT_R32_UINT, 0);
+            ctx.VSSetShader(&self.shaders.vertex, Some(&[]));
+            ctx.PSSetShader(&self.shaders.pixel, Some(&[]));
+
+            ctx.DrawIndexed(mesh.indices.len() as _, 0, 0);
         }
 
         Ok(())
@@ -234,6 +258,13 @@ impl DirectX11Renderer {
 }
 
 impl DirectX11Renderer {
+    /// Configures output for a floating point (scRGB) back buffer.
+    /// Pass the paper-white level in nits to scale colors so white matches SDR content,
+    /// or `None` for a regular sRGB back buffer.
+    pub fn set_hdr_paper_white(&mut self, nits: Option<f32>) {
+        self.hdr_scale = nits.map(|nits| nits / 80.0);
+    }
+
     #[inline]
     fn get_screen_size(&self) -> (f32, f32) {
         let mut rect = RECT::default();
@@ -91,7 +91,7 @@ fn get_vfunction(index: isize, types: Vec<Type>, ret_type: &ReturnType) -> Token
 
     quote! {
         std::mem::transmute::<_, extern "fastcall" fn(*const Self, #(#types),*) -> #ret_type> (
-           (*std::mem::transmute::<_, *const *const usize>(self)).offset(#index).read()
+           (*(self as *const Self).cast::<*const usize>()).offset(#index).read()
         )
     }
 }
@@ -36,7 +36,7 @@ fn has_vmt_field(fields: &FieldsNamed) -> bool {
     fields
         .named
         .iter()
-        .any(|field| field.ident.clone().map_or(false, |ident| ident == "__vmt"))
+        .any(|field| field.ident.as_ref().is_some_and(|ident| ident == "__vmt"))
 }
 
 fn add_repr_c(item: &mut ItemStruct) {
@@ -0,0 +1,35 @@
+use memory_macros::vmt;
+use std::mem::{align_of, offset_of, size_of};
+
+#[vmt]
+struct Entity {
+    health: i32,
+    team: u8,
+    origin: [f32; 3],
+}
+
+#[vmt]
+struct Empty {}
+
+#[test]
+fn vmt_is_first_field() {
+    assert_eq!(offset_of!(Entity, __vmt), 0);
+    assert_eq!(offset_of!(Empty, __vmt), 0);
+}
+
+#[test]
+fn fields_keep_declaration_order() {
+    let vmt_size = size_of::<usize>();
+
+    assert_eq!(offset_of!(Entity, health), vmt_size);
+    assert_eq!(offset_of!(Entity, team), vmt_size + size_of::<i32>());
+    assert_eq!(offset_of!(Entity, origin), vmt_size + 2 * align_of::<f32>());
+}
+
+#[test]
+fn struct_is_sized_like_repr_c() {
+    assert_eq!(size_of::<Empty>(), size_of::<usize>());
+    let unpadded =
+        size_of::<usize>() + size_of::<i32>() + align_of::<f32>() + size_of::<[f32; 3]>();
+    assert_eq!(size_of::<Entity>(), unpadded.next_multiple_of(align_of::<usize>()));
+}